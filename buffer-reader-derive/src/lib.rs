@@ -0,0 +1,59 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Derives `buffer_reader::FromBufferReader` for a struct of primitive integer fields, reading each
+/// field in order as a big-endian integer with `BufferReader::read_int_be`. If any field fails to
+/// read, the reader is left where it was before the struct.
+///
+/// Generic structs, including ones with lifetime parameters, are not supported, since every field
+/// has to be a concrete integer type anyway. Deriving `ReadBe` for one is a compile error.
+#[proc_macro_derive(ReadBe)]
+pub fn derive_read_be(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(&input.generics, "ReadBe does not support generic structs")
+            .to_compile_error()
+            .into();
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return syn::Error::new_spanned(&input, "ReadBe can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let body = match fields {
+        Fields::Named(fields) => {
+            let reads = fields.named.iter().map(|field| {
+                let ident = &field.ident;
+                let ty = &field.ty;
+                quote! { #ident: br.read_int_be::<#ty>()? }
+            });
+            quote! { #name { #(#reads),* } }
+        }
+        Fields::Unnamed(fields) => {
+            let reads = fields.unnamed.iter().map(|field| {
+                let ty = &field.ty;
+                quote! { br.read_int_be::<#ty>()? }
+            });
+            quote! { #name(#(#reads),*) }
+        }
+        Fields::Unit => quote! { #name },
+    };
+
+    quote! {
+        impl<'a> ::buffer_reader::FromBufferReader<'a> for #name {
+            fn from_buffer_reader(
+                br: &mut ::buffer_reader::BufferReader<'a>,
+            ) -> ::std::io::Result<Self> {
+                br.transaction(|br| Ok(#body))
+            }
+        }
+    }
+    .into()
+}
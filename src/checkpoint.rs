@@ -0,0 +1,25 @@
+/// A saved cursor position returned by `BufferReader::checkpoint`. It can be passed back to the same
+/// reader to rewind to that position with `BufferReader::restore`, or to look at the bytes consumed
+/// since it was taken.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checkpoint {
+    pub(crate) position: usize,
+}
+
+impl Checkpoint {
+    /// Returns the absolute position the checkpoint was taken at.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// The state of a `BufferReader` returned by `BufferReader::state`. With the `serde` feature it can
+/// be serialized, so a long parse of a file that doesn't change can be saved and resumed later with
+/// `BufferReader::restore_state`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReaderState {
+    /// The absolute position of the cursor in the original slice.
+    pub position: usize,
+}
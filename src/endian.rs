@@ -0,0 +1,253 @@
+use bytemuck::{AnyBitPattern, Pod, Zeroable};
+
+/// The byte order of a value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Endianness {
+    /// The byte order of the target the crate was compiled for.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endianness = Endianness::Little;
+    /// The byte order of the target the crate was compiled for.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endianness = Endianness::Big;
+}
+
+macro_rules! big_endian_type {
+    ($name:ident, $int:ty) => {
+        /// A big-endian
+        #[doc = concat!("`", stringify!($int), "`")]
+        /// stored as raw bytes, for use as a field type in structs read with `read_t`. The value is
+        /// only byte-swapped when it is accessed with `get`, so reads stay zero-copy. Because it is
+        /// stored as a byte array, it has an alignment of 1.
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name([u8; std::mem::size_of::<$int>()]);
+
+        impl $name {
+            /// Returns the native-endian value of this field.
+            #[inline(always)]
+            pub fn get(self) -> $int {
+                <$int>::from_be_bytes(self.0)
+            }
+        }
+
+        // SAFETY: The type is a transparent wrapper around a byte array, so it has no padding and
+        // every bit pattern is valid.
+        unsafe impl Zeroable for $name {}
+        unsafe impl Pod for $name {}
+    };
+}
+
+big_endian_type!(U16Be, u16);
+big_endian_type!(U32Be, u32);
+big_endian_type!(U64Be, u64);
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A primitive integer type that can be built from its little or big-endian bytes. This is used by
+/// `BufferReader::read_int_le` and `BufferReader::read_int_be`, and is sealed, so it can't be
+/// implemented outside of this crate.
+pub trait IntFromBytes: sealed::Sealed + Copy {
+    /// The byte array the integer is read from.
+    type Bytes: AnyBitPattern;
+    /// Returns the integer represented by `bytes` in little-endian order.
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+    /// Returns the integer represented by `bytes` in big-endian order.
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! int_from_bytes {
+    ($($int:ty),*) => {
+        $(
+            impl sealed::Sealed for $int {}
+
+            impl IntFromBytes for $int {
+                type Bytes = [u8; std::mem::size_of::<$int>()];
+
+                #[inline(always)]
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$int>::from_le_bytes(bytes)
+                }
+
+                #[inline(always)]
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$int>::from_be_bytes(bytes)
+                }
+            }
+        )*
+    };
+}
+
+int_from_bytes!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A type that can reverse its own byte order. This is used by `BufferReader::read_t_endian` to
+/// convert a value read in a foreign byte order. It is implemented for the primitive integer types,
+/// and can be implemented for structs by swapping each field.
+pub trait SwapBytes: Copy {
+    /// Returns `self` with the byte order of each field reversed.
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! swap_bytes {
+    ($($int:ty),*) => {
+        $(
+            impl SwapBytes for $int {
+                #[inline(always)]
+                fn swap_bytes(self) -> Self {
+                    <$int>::swap_bytes(self)
+                }
+            }
+        )*
+    };
+}
+
+swap_bytes!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BufferReader;
+    use bytemuck::AnyBitPattern;
+    use std::io::ErrorKind;
+
+    #[repr(C)]
+    #[derive(Copy, Clone, AnyBitPattern)]
+    struct BeHeader {
+        magic: U32Be,
+        version: U16Be,
+        len: U64Be,
+    }
+
+    #[test]
+    fn read_big_endian_fields() {
+        let bytes = b"\x12\x34\x56\x78\x00\x02\x00\x00\x00\x00\x00\x00\x01\x00";
+        let mut br = BufferReader::new(bytes);
+        let header = br.read_t::<BeHeader>().unwrap();
+
+        assert_eq!(header.magic.get(), 0x12345678);
+        assert_eq!(header.version.get(), 2);
+        assert_eq!(header.len.get(), 0x100);
+        assert!(br.is_empty());
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone, Debug, PartialEq, AnyBitPattern)]
+    struct Magic {
+        value: u32,
+    }
+
+    impl SwapBytes for Magic {
+        fn swap_bytes(self) -> Self {
+            Magic {
+                value: self.value.swap_bytes(),
+            }
+        }
+    }
+
+    #[test]
+    fn read_t_endian() {
+        let mut br = BufferReader::new(b"\x78\x56\x34\x12\x12\x34\x56\x78");
+        let expected = Magic { value: 0x12345678 };
+
+        assert_eq!(br.read_t_endian::<Magic>(Endianness::Little).unwrap(), expected);
+        assert_eq!(br.read_t_endian::<Magic>(Endianness::Big).unwrap(), expected);
+        assert!(br.read_t_endian::<u8>(Endianness::Big).is_err());
+    }
+
+    #[test]
+    fn peek_u32_both() {
+        let br = BufferReader::new(b"\xFF\x12\x34\x56\x78");
+        assert_eq!(br.peek_u32_both(1).unwrap(), (0x78563412, 0x12345678));
+        assert!(br.peek_u32_both(2).is_err());
+        assert_eq!(br.len(), 5);
+    }
+
+    #[test]
+    fn detect_endianness() {
+        let le = BufferReader::new(b"II*\x00");
+        let be = BufferReader::new(b"MM\x00*");
+        let marker_le = u32::from_le_bytes(*b"II*\x00");
+        let marker_be = u32::from_be_bytes(*b"MM\x00*");
+
+        assert_eq!(le.detect_endianness(marker_le, marker_be).unwrap(), Endianness::Little);
+        assert_eq!(be.detect_endianness(marker_le, marker_be).unwrap(), Endianness::Big);
+        assert_eq!(le.len(), 4);
+        assert_eq!(be.len(), 4);
+    }
+
+    #[test]
+    fn detect_endianness_unknown() {
+        let br = BufferReader::new(b"\x12\x34\x56\x78");
+        let err = br.detect_endianness(0xFEEDFACE, 0xFEEDFACE).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_int_le() {
+        let mut br = BufferReader::new(b"\x34\x12\x08\x07\x06\x05\x04\x03\x02\x01");
+
+        assert_eq!(br.read_int_le::<u16>().unwrap(), 0x1234);
+        assert_eq!(br.read_int_le::<u64>().unwrap(), 0x0102030405060708);
+        assert!(br.read_int_le::<u8>().is_err());
+    }
+
+    #[test]
+    fn read_with_endianness() {
+        let mut br = BufferReader::new(b"\x34\x12\x12\x34\x04\x03\x02\x01\x01\x02\x03\x04\xFF\xFE");
+
+        assert_eq!(br.read_u16(Endianness::Little).unwrap(), 0x1234);
+        assert_eq!(br.read_u16(Endianness::Big).unwrap(), 0x1234);
+        assert_eq!(br.read_u32(Endianness::Little).unwrap(), 0x01020304);
+        assert_eq!(br.read_u32(Endianness::Big).unwrap(), 0x01020304);
+        assert_eq!(br.read_i16(Endianness::Big).unwrap(), -2);
+        assert!(br.read_int::<u64>(Endianness::Little).is_err());
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_fixed_order() {
+        let mut br = BufferReader::new(b"\x34\x12\x12\x34\x04\x03\x02\x01\x01\x02\x03\x04\xFE\xFF\xFF\xFF");
+
+        assert_eq!(br.read_u16_le().unwrap(), 0x1234);
+        assert_eq!(br.read_u16_be().unwrap(), 0x1234);
+        assert_eq!(br.read_u32_le().unwrap(), 0x01020304);
+        assert_eq!(br.read_i32_be().unwrap(), 0x01020304);
+        assert_eq!(br.read_i32_le().unwrap(), -2);
+        assert!(br.read_u64_be().is_err());
+
+        let mut br = BufferReader::new(&[0xFF; 32]);
+        assert_eq!(br.read_i64_le().unwrap(), -1);
+        assert_eq!(br.read_u64_be().unwrap(), u64::MAX);
+        assert_eq!(br.read_i128_be().unwrap(), -1);
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_narrowed() {
+        let mut br = BufferReader::new(b"\xFF\x00\x00\x00\x00\x01\x00\x00\x34\x12\x00\x00");
+
+        assert_eq!(br.read_u8_from_u32_le().unwrap(), 0xFF);
+        let err = br.read_u8_from_u32_le().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(br.position(), 4);
+        assert_eq!(br.read_u16_from_u32_le().unwrap(), 0x100);
+        assert_eq!(br.read_u16_from_u32_le().unwrap(), 0x1234);
+        assert!(br.read_u32_from_u64_le().is_err());
+    }
+
+    #[test]
+    fn read_int_be() {
+        let mut br = BufferReader::new(b"\x12\x34\x01\x02\x03\x04\x05\x06\x07\x08");
+
+        assert_eq!(br.read_int_be::<u16>().unwrap(), 0x1234);
+        assert_eq!(br.read_int_be::<u64>().unwrap(), 0x0102030405060708);
+        assert!(br.is_empty());
+    }
+}
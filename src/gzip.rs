@@ -0,0 +1,76 @@
+/// The metadata from the header of a GZIP member, as returned by
+/// `BufferReader::read_gzip_member`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GzipHeader {
+    /// The modification time of the original file, in seconds since the Unix epoch, or 0 if it was
+    /// not set.
+    pub mtime: u32,
+    /// The operating system the member was compressed on.
+    pub os: u8,
+    /// The extra field, if present.
+    pub extra: Option<Vec<u8>>,
+    /// The original file name without the terminating NUL, if present.
+    pub filename: Option<Vec<u8>>,
+    /// The comment without the terminating NUL, if present.
+    pub comment: Option<Vec<u8>>,
+}
+
+impl From<&flate2::GzHeader> for GzipHeader {
+    fn from(header: &flate2::GzHeader) -> Self {
+        GzipHeader {
+            mtime: header.mtime(),
+            os: header.operating_system(),
+            extra: header.extra().map(<[u8]>::to_vec),
+            filename: header.filename().map(<[u8]>::to_vec),
+            comment: header.comment().map(<[u8]>::to_vec),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufferReader;
+    use std::io::{ErrorKind, Write};
+
+    fn gzip(payload: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::GzBuilder::new()
+            .filename("hello.txt")
+            .comment("greeting")
+            .mtime(1234)
+            .write(Vec::new(), Default::default());
+        encoder.write_all(payload).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn read_gzip_member() {
+        let mut members = gzip(b"Hello, World!");
+        let first_len = members.len();
+        members.extend(gzip(b"Goodbye!"));
+
+        let mut br = BufferReader::new(&members);
+        let (header, payload) = br.read_gzip_member().unwrap();
+        assert_eq!(header.mtime, 1234);
+        assert_eq!(header.filename.as_deref(), Some(&b"hello.txt"[..]));
+        assert_eq!(header.comment.as_deref(), Some(&b"greeting"[..]));
+        assert_eq!(header.extra, None);
+        assert_eq!(payload, b"Hello, World!");
+        assert_eq!(br.position(), first_len);
+
+        let (_, payload) = br.read_gzip_member().unwrap();
+        assert_eq!(payload, b"Goodbye!");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_gzip_member_truncated() {
+        let member = gzip(b"Hello, World!");
+        let mut br = BufferReader::new(&member[..member.len() - 4]);
+
+        assert!(br.read_gzip_member().is_err());
+        assert_eq!(br.position(), 0);
+
+        let mut br = BufferReader::new(b"not gzip data");
+        assert_eq!(br.read_gzip_member().unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+}
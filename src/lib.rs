@@ -1,9 +1,79 @@
-use std::io::{Error, ErrorKind};
+use std::io::{Error, ErrorKind, IoSliceMut};
 use bytemuck::AnyBitPattern;
 
+// Lets the derive macros refer to `::buffer_reader` in this crate's own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as buffer_reader;
+
+mod checkpoint;
+pub mod endian;
+#[cfg(feature = "flate")]
+mod gzip;
+mod scope;
+mod string;
+mod tokenizer;
+#[cfg(feature = "trace")]
+mod trace;
+mod writer;
+
+pub use checkpoint::{Checkpoint, ReaderState};
+pub use endian::{Endianness, IntFromBytes, SwapBytes, U16Be, U32Be, U64Be};
+#[cfg(feature = "flate")]
+pub use gzip::GzipHeader;
+#[cfg(feature = "derive")]
+pub use buffer_reader_derive::ReadBe;
+pub use scope::RecordScope;
+pub use string::StringEncoding;
+pub use tokenizer::RecordTokenizer;
+#[cfg(feature = "trace")]
+pub use trace::{ReadEvent, ReadKind};
+pub use writer::BufferWriter;
+
+// Generates a method for reading each integer type in a byte order chosen at runtime.
+macro_rules! read_int_endian {
+    ($($name:ident: $int:ty),*) => {
+        $(
+            /// Returns the next `n` bytes in the slice as a
+            #[doc = concat!("`", stringify!($int), "`")]
+            /// in the byte order `e`, Where `n` is the size of the integer, and then advances the
+            /// slice by `n`. Function will fail if there are not enough bytes left in the buffer.
+            #[inline(always)]
+            pub fn $name(&mut self, e: Endianness) -> std::io::Result<$int> {
+                self.read_int(e)
+            }
+        )*
+    };
+}
+
+// Generates a method for reading each integer type in a fixed byte order.
+macro_rules! read_int_ordered {
+    ($($name:ident: $int:ty, $order:ident, $doc:literal);* $(;)?) => {
+        $(
+            /// Returns the next `n` bytes in the slice as a
+            #[doc = concat!($doc, " `", stringify!($int), "`,")]
+            /// Where `n` is the size of the integer, and then advances the slice by `n`. Function will
+            /// fail if there are not enough bytes left in the buffer.
+            #[inline(always)]
+            pub fn $name(&mut self) -> std::io::Result<$int> {
+                self.read_int(Endianness::$order)
+            }
+        )*
+    };
+}
+
 /// A structure used for getting references to C structures in a contiguous buffer of memory.
+///
+/// # Errors
+///
+/// Every `read_*` method checks that there are enough bytes left in the buffer before it advances.
+/// If a read fails, the reader is left exactly where it was before the call, so the caller can fall
+/// back to a different parse from the same position.
 pub struct BufferReader<'a> {
+    base: &'a [u8],
     buffer: &'a [u8],
+    end: usize,
+    #[cfg(feature = "trace")]
+    trace: std::sync::Mutex<Vec<ReadEvent>>,
 }
 
 impl<'a> BufferReader<'a> {
@@ -11,26 +81,272 @@ impl<'a> BufferReader<'a> {
     #[inline(always)]
     pub fn new(slice: &'a [u8]) -> Self {
         BufferReader {
+            base: slice,
             buffer: slice,
+            end: slice.len(),
+            #[cfg(feature = "trace")]
+            trace: Default::default(),
         }
     }
+    /// Returns a new `BufferReader<'a>` for the provided slice, with the cursor already `offset` bytes
+    /// into it, so `position` reports `offset`. Function will fail if `offset` is past the end of the
+    /// slice.
+    pub fn new_at(slice: &'a [u8], offset: usize) -> std::io::Result<Self> {
+        let mut br = BufferReader::new(slice);
+        br.check_available(offset)?;
+        br.buffer = &slice[offset..];
+        Ok(br)
+    }
+    /// Returns a new `BufferReader<'a>` for the provided slice, with the cursor at the position saved
+    /// in `state`. The slice should be the same one the state was taken from. Function will fail if
+    /// the saved position is past the end of the slice.
+    #[inline(always)]
+    pub fn restore_state(slice: &'a [u8], state: ReaderState) -> std::io::Result<Self> {
+        BufferReader::new_at(slice, state.position)
+    }
+    /// Returns a new `BufferReader<'a>` for anything that can be borrowed as a byte slice, such as
+    /// `Vec<u8>`, `String`, `[u8; N]` or `Box<[u8]>`.
+    #[inline(always)]
+    pub fn from_ref<B: AsRef<[u8]> + ?Sized>(b: &'a B) -> Self {
+        BufferReader::new(b.as_ref())
+    }
+    /// Returns a new `BufferReader<'a>` over the contents of a memory-mapped file. This is the
+    /// recommended way to parse huge files, since the OS only pages in the parts of the file that
+    /// are actually read.
+    ///
+    /// ```no_run
+    /// use buffer_reader::BufferReader;
+    /// use memmap2::Mmap;
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let file = File::open("foo.bin")?;
+    ///     // SAFETY: The file must not be modified while it is mapped.
+    ///     let mmap = unsafe { Mmap::map(&file)? };
+    ///     let mut br = BufferReader::from_mmap(&mmap);
+    ///
+    ///     let magic = br.read_bytes(4)?;
+    ///     println!("Magic: {:?}", magic);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "mmap")]
+    #[inline(always)]
+    pub fn from_mmap(mmap: &'a memmap2::Mmap) -> Self {
+        BufferReader::from_ref(mmap)
+    }
     /// Returns a reference to the next `n` bytes in the slice as a reference to `T`. and then
     /// advances the slice by the size of `T` in bytes. Function will fail if the length of the underlying
     /// slice is less than the size of `T`.
     pub fn read_t<T: AnyBitPattern>(&mut self) -> std::io::Result<&'a T> {
         let size = std::mem::size_of::<T>();
-        self.check_available(size)?;
-        let slice = self.advance(size);
+        self.check_available_as::<T>(size)?;
+        let slice = self.advance_as::<T>(size);
         // SAFETY: We know that the buffer passed back from `self.advance(size)?` is the size of T,
         // so we will assume that it's a valid T. This function is now considered safe, since we are
         // now requiring bytemuck and the `AnyBitPattern` trait.
         Ok(unsafe { &*(slice.as_ptr() as *const T) })
     }
+    /// Returns a copy of the next `T` in the buffer, stored in the byte order `order`, and then advances
+    /// the slice by the size of `T`. If `order` is not the native byte order, the value is converted
+    /// with `SwapBytes`. Because the value is copied out, the buffer does not need to be aligned.
+    /// Function will fail if there are not enough bytes left in the buffer.
+    pub fn read_t_endian<T: AnyBitPattern + SwapBytes>(
+        &mut self,
+        order: Endianness,
+    ) -> std::io::Result<T> {
+        let t: T = bytemuck::pod_read_unaligned(self.read_bytes(std::mem::size_of::<T>())?);
+        Ok(if order == Endianness::NATIVE {
+            t
+        } else {
+            t.swap_bytes()
+        })
+    }
+    /// Returns a reference to the first size of `T` bytes of a `declared_size` byte record as a
+    /// reference to `T`, and then advances the slice by `declared_size`, skipping any bytes after `T`.
+    /// This handles formats where newer versions append fields to a record. Function will fail
+    /// without advancing if `declared_size` is less than the size of `T`, or if there are fewer than
+    /// `declared_size` bytes left in the buffer.
+    pub fn read_t_exact<T: AnyBitPattern>(&mut self, declared_size: usize) -> std::io::Result<&'a T> {
+        let size = std::mem::size_of::<T>();
+        if declared_size < size {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "BufferReader declared size {} is smaller than the size of `{}` ({})",
+                    declared_size,
+                    std::any::type_name::<T>(),
+                    size,
+                ),
+            ));
+        }
+
+        self.check_available_as::<T>(declared_size)?;
+        let t = self.read_t()?;
+        self.advance(declared_size - size);
+        Ok(t)
+    }
+    /// Same as `read_t_exact`, but also checks that every byte of the record after `T` is zero, for
+    /// formats that pad records with mandatory zero bytes. Function will fail without advancing if
+    /// any padding byte is not zero, or under the same conditions as `read_t_exact`.
+    pub fn read_t_padded<T: AnyBitPattern>(&mut self, total_size: usize) -> std::io::Result<&'a T> {
+        let size = std::mem::size_of::<T>();
+        if let Some(padding_len) = total_size.checked_sub(size) {
+            let padding = self.peek_bytes(size, padding_len)?;
+            if let Some(i) = padding.iter().position(|&b| b != 0) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "BufferReader padding byte at offset {:#X} is {:#04X}, not zero",
+                        self.position() + size + i,
+                        padding[i],
+                    ),
+                ));
+            }
+        }
+
+        self.read_t_exact(total_size)
+    }
+    /// Same as `read_t`, but also returns the bytes that `T` was read from. Both references point to
+    /// the same region of the buffer.
+    pub fn read_t_with_bytes<T: AnyBitPattern>(&mut self) -> std::io::Result<(&'a T, &'a [u8])> {
+        let bytes = self.peek_bytes(0, std::mem::size_of::<T>())?;
+        Ok((self.read_t()?, bytes))
+    }
+    /// Same as `read_t`, but first checks that the size of `T` is `expected_size`. This guards
+    /// against a struct definition silently diverging from the on-disk record size because of
+    /// padding or packing. Function will fail without advancing if the sizes do not match.
+    pub fn read_t_sized<T: AnyBitPattern>(&mut self, expected_size: usize) -> std::io::Result<&'a T> {
+        let size = std::mem::size_of::<T>();
+        if size != expected_size {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "BufferReader expected type `{}` to be {} bytes, but it is {} bytes",
+                    std::any::type_name::<T>(),
+                    expected_size,
+                    size,
+                ),
+            ));
+        }
+
+        self.read_t()
+    }
+    /// Returns a reference to the next `n` bytes in the slice as a reference to `T` if `validate`
+    /// returns true for it, and then advances the slice by the size of `T` in bytes. Function will
+    /// fail without advancing if there are not enough bytes left in the buffer, or if `validate`
+    /// returns false.
+    pub fn read_t_validated<T: AnyBitPattern>(
+        &mut self,
+        validate: impl Fn(&T) -> bool,
+    ) -> std::io::Result<&'a T> {
+        let t = self.peek_t::<T>(0)?;
+        if !validate(t) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "BufferReader read value failed validation",
+            ));
+        }
+
+        self.advance(std::mem::size_of::<T>());
+        Ok(t)
+    }
+    /// Returns references to an `A` followed directly by a `B` if `validate` returns true for them, and
+    /// then advances the slice past both. This is useful for checks that span two structures, such
+    /// as a header whose offset must point past the structure after it. Function will fail without
+    /// advancing if there are not enough bytes left in the buffer, or if `validate` returns false.
+    pub fn read_pair_validated<A: AnyBitPattern, B: AnyBitPattern>(
+        &mut self,
+        validate: impl Fn(&A, &B) -> bool,
+    ) -> std::io::Result<(&'a A, &'a B)> {
+        let a_size = std::mem::size_of::<A>();
+        let a = self.peek_t::<A>(0)?;
+        let b = self.peek_t::<B>(a_size)?;
+        if !validate(a, b) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "BufferReader read pair failed validation",
+            ));
+        }
+
+        self.advance_as::<A>(a_size);
+        self.advance_as::<B>(std::mem::size_of::<B>());
+        Ok((a, b))
+    }
+    /// Returns a copy of the next `T` in the buffer if its bytes are a valid bit pattern for `T`, and
+    /// then advances the slice by the size of `T` in bytes. The value is copied out with
+    /// `bytemuck::checked::try_pod_read_unaligned`, so the buffer does not need to be aligned. Function
+    /// will fail without advancing if there are not enough bytes left in the buffer, or if the bytes
+    /// are not a valid `T`.
+    pub fn read_t_checked_copy<T: bytemuck::CheckedBitPattern>(&mut self) -> std::io::Result<T> {
+        let size = std::mem::size_of::<T::Bits>();
+        let t = bytemuck::checked::try_pod_read_unaligned(self.peek_bytes(0, size)?).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "BufferReader bytes are not a valid `{}`: {}",
+                    std::any::type_name::<T>(),
+                    e,
+                ),
+            )
+        })?;
+        self.advance_as::<T>(size);
+        Ok(t)
+    }
+    /// Same as `read_t`, but does not check that there are enough bytes left in the buffer. This is
+    /// for hot loops where the total length has already been validated.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that there are at least size of `T` bytes left in the buffer, for
+    /// example by calling `require` before the loop.
+    pub unsafe fn read_t_unchecked<T: AnyBitPattern>(&mut self) -> &'a T {
+        // SAFETY: The caller guarantees there is room in the buffer.
+        let slice = unsafe { self.advance_unchecked::<T>(std::mem::size_of::<T>()) };
+        // SAFETY: See read_t
+        unsafe { &*(slice.as_ptr() as *const T) }
+    }
+    /// Checks that the next `n` bytes in the slice are equal to `expected` when read as a `T`, Where
+    /// n is the size of `T`, and then advances the slice by the size of `T` in bytes. This is useful
+    /// for checking magic numbers. Function will fail without advancing if there are not enough
+    /// bytes left in the buffer, or if the value does not match.
+    pub fn expect_t<T: AnyBitPattern + PartialEq>(&mut self, expected: &T) -> std::io::Result<()> {
+        let size = std::mem::size_of::<T>();
+        let t: T = bytemuck::pod_read_unaligned(self.peek_bytes(0, size)?);
+        if t != *expected {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "BufferReader value at offset {:#X} does not match the expected `{}`",
+                    self.position(),
+                    std::any::type_name::<T>(),
+                ),
+            ));
+        }
+
+        self.advance_as::<T>(size);
+        Ok(())
+    }
+    /// Returns a copy of the next `n` bytes in the slice as a `T`, Where n is the size of `T`, and then
+    /// advances the slice by the size of `T` in bytes. If there are not enough bytes left in the
+    /// buffer, `default` is returned and the slice is not advanced. This is useful for trailing
+    /// fields that older versions of a format may not have.
+    pub fn read_t_copy_or<T: AnyBitPattern>(&mut self, default: T) -> T {
+        let size = std::mem::size_of::<T>();
+        if self.check_available(size).is_err() {
+            return default;
+        }
+
+        bytemuck::pod_read_unaligned(self.advance(size))
+    }
     /// Returns a reference to the next `n` bytes in the slice as a reference to `T`, Where n is the
-    /// size of `T`. Function will fail if there are not enough bytes left in the buffer.
+    /// size of `T`. Function will fail if there are not enough bytes left in the buffer. A zero sized
+    /// `T` can be peeked at any `start` up to and including the end of the buffer.
     pub fn peek_t<T: AnyBitPattern>(&self, start: usize) -> std::io::Result<&'a T> {
-        let end = start + std::mem::size_of::<T>();
-        self.check_available(end)?;
+        let end = start.saturating_add(std::mem::size_of::<T>());
+        self.check_available_as::<T>(end)?;
+        #[cfg(feature = "trace")]
+        self.record::<T>(ReadKind::Peek, start, end - start);
         let slice = &self.peek_remaining()[start..end];
         // SAFETY: See read_t
         Ok(unsafe { &*(slice.as_ptr() as *const T) })
@@ -39,21 +355,740 @@ impl<'a> BufferReader<'a> {
     /// advances the slice by the size of `T` * `len` in bytes. Function will fail if the length of
     /// the underlying slice is less than the size of `T`.
     pub fn read_slice_t<T: AnyBitPattern>(&mut self, len: usize) -> std::io::Result<&'a [T]> {
-        let size = len * std::mem::size_of::<T>();
-        self.check_available(size)?;
-        let slice = self.advance(size);
+        let size = Self::slice_size::<T>(len)?;
+        self.check_available_as::<[T]>(size)?;
+        let slice = self.advance_as::<[T]>(size);
         // SAFETY: See read_t
         Ok(unsafe { core::slice::from_raw_parts(slice.as_ptr() as *const T, len) })
     }
+    /// Returns a reference to the elements of `T` before the next all-zero element, and then advances
+    /// the slice past the elements and the zero element. This is useful for NULL terminated tables
+    /// of offsets or pointers. Function will fail without advancing if there is no zero element left
+    /// in the buffer.
+    pub fn read_slice_t_until_zero<T: AnyBitPattern>(&mut self) -> std::io::Result<&'a [T]> {
+        let size = std::mem::size_of::<T>();
+        if size == 0 {
+            return Ok(&[]);
+        }
+
+        let len = self
+            .buffer
+            .chunks_exact(size)
+            .position(|t| t.iter().all(|&b| b == 0))
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "BufferReader could not find a zero element in the remaining buffer",
+                )
+            })?;
+        let slice = self.read_slice_t(len)?;
+        self.advance_as::<T>(size);
+        Ok(slice)
+    }
+    /// Reads an unsigned LEB128 element count, and returns a reference to that many elements of `T`
+    /// following it, and then advances the slice past both. Function will fail without advancing if
+    /// the count is malformed, or if there are not enough bytes left in the buffer for the elements.
+    pub fn read_varint_prefixed_slice_t<T: AnyBitPattern>(&mut self) -> std::io::Result<&'a [T]> {
+        let (count, prefix_len) = self.decode_uleb128()?;
+        let count = usize::try_from(count).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        let slice = self.peek_slice_t(prefix_len, count)?;
+        self.advance(prefix_len);
+        self.advance_as::<[T]>(std::mem::size_of_val(slice));
+        Ok(slice)
+    }
+    /// Returns a reference to the next `count` elements of `T` and a reference to all of the bytes
+    /// after them, and then advances the slice to the end. Function will fail without advancing if
+    /// there are not enough bytes left in the buffer for the elements.
+    pub fn read_slice_t_then_rest<T: AnyBitPattern>(
+        &mut self,
+        count: usize,
+    ) -> std::io::Result<(&'a [T], &'a [u8])> {
+        let slice = self.read_slice_t(count)?;
+        Ok((slice, self.read_remaining()))
+    }
+    /// Returns a reference to a header `H` and to the `count` records `R` that directly follow it, and
+    /// then advances the slice past both. The size of the whole table is checked once, up front.
+    /// Function will fail without advancing if there are not enough bytes left in the buffer.
+    pub fn read_header_then_records<H: AnyBitPattern, R: AnyBitPattern>(
+        &mut self,
+        count: usize,
+    ) -> std::io::Result<(&'a H, &'a [R])> {
+        let header_size = std::mem::size_of::<H>();
+        let size = header_size.checked_add(Self::slice_size::<R>(count)?).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "BufferReader slice size would overflow a usize",
+            )
+        })?;
+        self.check_available_as::<(H, [R])>(size)?;
+        let (header, records) = self.advance_as::<(H, [R])>(size).split_at(header_size);
+        // SAFETY: The checked size covers the header followed by `count` records, so `header` is the
+        // size of H and `records` is the size of `count` elements of R. See read_t.
+        Ok(unsafe {
+            (
+                &*(header.as_ptr() as *const H),
+                core::slice::from_raw_parts(records.as_ptr() as *const R, count),
+            )
+        })
+    }
     /// Returns a reference to the next `n` bytes in the slice as a reference to `T`, Where `n` is the
     /// size of `T` * `len`. Function will fail if there are not enough bytes left in the buffer.
     pub fn peek_slice_t<T: AnyBitPattern>(&self, start: usize, len: usize) -> std::io::Result<&'a [T]> {
-        let end = start + (std::mem::size_of::<T>() * len);
-        self.check_available(end)?;
+        let end = start.saturating_add(Self::slice_size::<T>(len)?);
+        self.check_available_as::<[T]>(end)?;
+        #[cfg(feature = "trace")]
+        self.record::<[T]>(ReadKind::Peek, start, end - start);
         let slice = &self.peek_remaining()[start..end];
         // SAFETY: See read_t
         Ok(unsafe { core::slice::from_raw_parts(slice.as_ptr() as *const T, len) })
     }
+    /// Returns a reference to the next `n` bytes in the slice as a reference to `[T]`, Where `n` is
+    /// the size of `T` * `len`. This is the same as `peek_slice_t(0, len)`. Function will fail if
+    /// there are not enough bytes left in the buffer.
+    #[inline(always)]
+    pub fn peek_next_slice_t<T: AnyBitPattern>(&self, len: usize) -> std::io::Result<&'a [T]> {
+        self.peek_slice_t(0, len)
+    }
+    /// Returns a reference to all of the remaining bytes as a reference to `[T]`, without advancing.
+    /// A zero sized `T` returns an empty slice. Function will fail if the remaining length is not a
+    /// multiple of the size of `T`.
+    pub fn peek_remaining_slice_t<T: AnyBitPattern>(&self) -> std::io::Result<&'a [T]> {
+        let size = std::mem::size_of::<T>();
+        if size == 0 {
+            return Ok(&[]);
+        }
+        if !self.len().is_multiple_of(size) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "BufferReader remaining length {} is not a multiple of the size of `{}` ({})",
+                    self.len(),
+                    std::any::type_name::<T>(),
+                    size,
+                ),
+            ));
+        }
+
+        self.peek_slice_t(0, self.len() / size)
+    }
+    /// Returns a reference to all of the remaining bytes as a reference to `[T]`, without advancing.
+    /// Unlike `peek_remaining_slice_t`, this checks both the length and the alignment of the buffer
+    /// with `bytemuck::try_cast_slice`. Function will fail if the remaining length is not a multiple
+    /// of the size of `T`, or if the buffer is not aligned for `T`.
+    pub fn cast_remaining<T: AnyBitPattern>(&self) -> std::io::Result<&'a [T]> {
+        let slice = bytemuck::try_cast_slice(self.buffer).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "BufferReader could not cast the remaining buffer to `[{}]`: {}",
+                    std::any::type_name::<T>(),
+                    e,
+                ),
+            )
+        })?;
+        #[cfg(feature = "trace")]
+        self.record::<[T]>(ReadKind::Peek, 0, self.len());
+        Ok(slice)
+    }
+    /// Returns an owned `Vec<T>` of the next `count` elements of `T`, and then advances the slice by
+    /// the size of `T` * `count` in bytes. Each element is copied out with `pod_read_unaligned`, so
+    /// unlike `read_slice_t` the buffer does not need to be aligned for `T`. Function will fail if
+    /// there are not enough bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn read_vec_t<T: AnyBitPattern>(&mut self, count: usize) -> std::io::Result<Vec<T>> {
+        let size = Self::slice_size::<T>(count)?;
+        self.check_available_as::<[T]>(size)?;
+        let slice = self.advance(size);
+        let t_size = std::mem::size_of::<T>();
+        Ok((0..count)
+            .map(|i| bytemuck::pod_read_unaligned(&slice[i * t_size..(i + 1) * t_size]))
+            .collect())
+    }
+    /// Returns an owned `Vec<T>` of the next `count` elements of `T`, and then advances the slice by
+    /// the size of `T` * `count` in bytes. The elements are copied out of the possibly unaligned
+    /// buffer into the `Vec`, so they are always aligned for `T`, such as when the data needs to be
+    /// handed to an API that requires alignment. This is the same as `read_vec_t`. Function will fail
+    /// if there are not enough bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn read_vec_t_aligned<T: AnyBitPattern>(&mut self, count: usize) -> std::io::Result<Vec<T>> {
+        self.read_vec_t(count)
+    }
+    /// Same as `read_vec_t`, but the start of each element is `stride` bytes after the start of the
+    /// previous one, so the bytes after each element are skipped. The slice is advanced by `count` *
+    /// `stride` bytes. Function will fail without advancing if `stride` is less than the size of `T`,
+    /// or if there are fewer than `count` * `stride` bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn read_slice_t_strided<T: AnyBitPattern>(
+        &mut self,
+        count: usize,
+        stride: usize,
+    ) -> std::io::Result<Vec<T>> {
+        let t_size = std::mem::size_of::<T>();
+        if stride < t_size {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "BufferReader stride {} is smaller than the size of `{}` ({})",
+                    stride,
+                    std::any::type_name::<T>(),
+                    t_size,
+                ),
+            ));
+        }
+
+        let size = Self::elements_size(stride, count)?;
+        self.check_available_as::<[T]>(size)?;
+        let slice = self.advance_as::<[T]>(size);
+        Ok((0..count)
+            .map(|i| bytemuck::pod_read_unaligned(&slice[i * stride..i * stride + t_size]))
+            .collect())
+    }
+    /// Returns an owned `Vec` of the next `count` key and value pairs, where each key `K` is directly
+    /// followed by its value `V`, and then advances the slice past all of the pairs. Like
+    /// `read_vec_t`, the elements are copied out, so the buffer does not need to be aligned. Function
+    /// will fail without advancing if there are not enough bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn read_pairs_t<K: AnyBitPattern, V: AnyBitPattern>(
+        &mut self,
+        count: usize,
+    ) -> std::io::Result<Vec<(K, V)>> {
+        let k_size = std::mem::size_of::<K>();
+        let pair_size = k_size + std::mem::size_of::<V>();
+        let size = Self::elements_size(pair_size, count)?;
+        self.check_available(size)?;
+        let slice = self.advance(size);
+        Ok((0..count)
+            .map(|i| {
+                let (k, v) = slice[i * pair_size..(i + 1) * pair_size].split_at(k_size);
+                (bytemuck::pod_read_unaligned(k), bytemuck::pod_read_unaligned(v))
+            })
+            .collect())
+    }
+    /// Reads a little-endian `u16` count, followed by that many byte strings that are each prefixed by
+    /// their length as a little-endian `u16`, and returns references to the byte strings. The slice is
+    /// advanced past the whole list. Function will fail without advancing if the count or any length
+    /// runs past the end of the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn read_list_of_blobs_u16_le(&mut self) -> std::io::Result<Vec<&'a [u8]>> {
+        self.transaction(|br| {
+            let count = br.read_int_le::<u16>()?;
+            (0..count)
+                .map(|_| {
+                    let len = br.read_int_le::<u16>()?;
+                    br.read_bytes(len as usize)
+                })
+                .collect()
+        })
+    }
+    /// Reads tag-length-value records until the buffer is empty, and returns the tag and a reference to
+    /// the value of each one. Each record is a `u8` tag, a little-endian `u16` length, and that many
+    /// value bytes. Function will fail without advancing if the last record is truncated.
+    #[cfg(feature = "alloc")]
+    pub fn read_all_tlv_u8u16le(&mut self) -> std::io::Result<Vec<(u8, &'a [u8])>> {
+        self.transaction(|br| {
+            let mut records = Vec::new();
+            while !br.is_empty() {
+                let tag = br.read_byte()?;
+                let len = br.read_int_le::<u16>()?;
+                records.push((tag, br.read_bytes(len as usize)?));
+            }
+
+            Ok(records)
+        })
+    }
+    /// Reads a table of `count` little-endian `u32` offsets into the original slice, and returns the
+    /// region from each offset up to the next one, with the last region running to the end of the
+    /// buffer. The slice is advanced past the table only. Function will fail without advancing if the
+    /// table runs past the end of the buffer, or if the offsets are out of order or out of bounds.
+    #[cfg(feature = "alloc")]
+    pub fn read_by_offset_table_u32_le(&mut self, count: usize) -> std::io::Result<Vec<&'a [u8]>> {
+        let table = self.peek_bytes(0, Self::elements_size(4, count)?)?;
+        let mut offsets: Vec<usize> = table
+            .chunks_exact(4)
+            .map(|o| u32::from_le_bytes([o[0], o[1], o[2], o[3]]) as usize)
+            .collect();
+        offsets.push(self.end);
+        if offsets.windows(2).any(|w| w[0] > w[1]) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "BufferReader offset table entries are out of order or past the end of the buffer",
+            ));
+        }
+
+        self.advance(table.len());
+        Ok(offsets.windows(2).map(|w| &self.base[w[0]..w[1]]).collect())
+    }
+    /// Returns an owned `Vec` of `max_fields` optional fields, where bit `i` of `bitmap` (counting
+    /// from the least significant bit) says whether field `i` is present. A `T` is read for each
+    /// present field, and `None` is pushed for each absent one, so the slice is only advanced past the
+    /// present fields. Like `read_vec_t`, the fields are copied out, so the buffer does not need to be
+    /// aligned. Function will fail without advancing if `max_fields` is more than 32, or if there are
+    /// not enough bytes left in the buffer for the present fields.
+    #[cfg(feature = "alloc")]
+    pub fn read_optional_fields_t<T: AnyBitPattern>(
+        &mut self,
+        bitmap: u32,
+        max_fields: usize,
+    ) -> std::io::Result<Vec<Option<T>>> {
+        if max_fields > 32 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "BufferReader presence bitmap can describe at most 32 fields",
+            ));
+        }
+
+        self.transaction(|br| {
+            (0..max_fields)
+                .map(|i| {
+                    if bitmap & (1 << i) == 0 {
+                        return Ok(None);
+                    }
+
+                    let bytes = br.read_bytes(std::mem::size_of::<T>())?;
+                    Ok(Some(bytemuck::pod_read_unaligned(bytes)))
+                })
+                .collect()
+        })
+    }
+    /// Decodes a Consistent Overhead Byte Stuffing (COBS) frame starting at the current position and
+    /// ending at the next zero delimiter, and then advances the slice past the delimiter. Function
+    /// will fail if there is no delimiter left in the buffer, or if the frame is malformed.
+    #[cfg(feature = "alloc")]
+    pub fn read_cobs_frame(&mut self) -> std::io::Result<Vec<u8>> {
+        let frame_len = self.buffer.iter().position(|&b| b == 0).ok_or_else(|| {
+            Error::new(
+                ErrorKind::UnexpectedEof,
+                "BufferReader could not find a COBS frame delimiter",
+            )
+        })?;
+        let frame = &self.buffer[..frame_len];
+        let malformed = || Error::new(ErrorKind::InvalidData, "BufferReader COBS frame is malformed");
+        if frame.is_empty() {
+            return Err(malformed());
+        }
+
+        let mut decoded = Vec::with_capacity(frame_len);
+        let mut i = 0;
+        while i < frame.len() {
+            let code = frame[i] as usize;
+            let end = i + code;
+            if end > frame.len() {
+                return Err(malformed());
+            }
+
+            decoded.extend_from_slice(&frame[i + 1..end]);
+            i = end;
+            // A code of 0xFF means a full block with no zero following it.
+            if code != 0xFF && i < frame.len() {
+                decoded.push(0);
+            }
+        }
+
+        self.advance(frame_len + 1);
+        Ok(decoded)
+    }
+    /// Decodes run-length encoded data from the buffer until `decoded_len` bytes have been produced,
+    /// and then advances the slice past the encoded bytes that were consumed.
+    ///
+    /// The data is a sequence of packets, each starting with a control byte whose low 7 bits are a
+    /// count `N`. If the high bit of the control byte is set, the next byte is repeated `N` times.
+    /// Otherwise the next `N` bytes are copied as literals.
+    ///
+    /// Function will fail without advancing if the encoded data ends early, or if a packet would
+    /// produce more than `decoded_len` bytes.
+    #[cfg(feature = "alloc")]
+    pub fn read_rle_decode(&mut self, decoded_len: usize) -> std::io::Result<Vec<u8>> {
+        self.transaction(|br| {
+            let mut decoded = Vec::with_capacity(decoded_len.min(br.len()));
+            while decoded.len() < decoded_len {
+                let control = br.read_byte()?;
+                let count = (control & 0x7F) as usize;
+                if decoded.len() + count > decoded_len {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "BufferReader RLE packet overruns the decoded length",
+                    ));
+                }
+
+                if control & 0x80 != 0 {
+                    let byte = br.read_byte()?;
+                    decoded.resize(decoded.len() + count, byte);
+                } else {
+                    decoded.extend_from_slice(br.read_bytes(count)?);
+                }
+            }
+
+            Ok(decoded)
+        })
+    }
+    /// Decodes the next `hex_char_count` ASCII hex characters into `hex_char_count / 2` bytes, and
+    /// then advances the slice past the characters. Both upper and lower case digits are accepted.
+    /// Function will fail without advancing if `hex_char_count` is odd, if there are not enough bytes
+    /// left in the buffer, or if any of the characters is not a hex digit.
+    #[cfg(feature = "alloc")]
+    pub fn read_hex_bytes(&mut self, hex_char_count: usize) -> std::io::Result<Vec<u8>> {
+        if !hex_char_count.is_multiple_of(2) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "BufferReader hex character count must be even",
+            ));
+        }
+
+        let hex = self.peek_bytes(0, hex_char_count)?;
+        let digit = |c: u8| {
+            (c as char).to_digit(16).map(|d| d as u8).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("BufferReader found non-hex character {:#04X}", c),
+                )
+            })
+        };
+        let bytes = hex
+            .chunks_exact(2)
+            .map(|pair| Ok(digit(pair[0])? << 4 | digit(pair[1])?))
+            .collect::<std::io::Result<Vec<u8>>>()?;
+
+        self.advance(hex_char_count);
+        Ok(bytes)
+    }
+    /// Returns the next `n` bytes in the slice as a little-endian `I`, Where `n` is the size of `I`,
+    /// and then advances the slice by the size of `I` in bytes. Function will fail if there are not
+    /// enough bytes left in the buffer.
+    pub fn read_int_le<I: IntFromBytes>(&mut self) -> std::io::Result<I> {
+        Ok(I::from_le_bytes(*self.read_t::<I::Bytes>()?))
+    }
+    /// Returns the next `n` bytes in the slice as a big-endian `I`, Where `n` is the size of `I`,
+    /// and then advances the slice by the size of `I` in bytes. Function will fail if there are not
+    /// enough bytes left in the buffer.
+    pub fn read_int_be<I: IntFromBytes>(&mut self) -> std::io::Result<I> {
+        Ok(I::from_be_bytes(*self.read_t::<I::Bytes>()?))
+    }
+    /// Returns the next `n` bytes in the slice as an `I` in the byte order `e`, Where `n` is the size
+    /// of `I`, and then advances the slice by the size of `I` in bytes. This is useful when the byte
+    /// order is only known at runtime, such as after `detect_endianness`. Function will fail if there
+    /// are not enough bytes left in the buffer.
+    pub fn read_int<I: IntFromBytes>(&mut self, e: Endianness) -> std::io::Result<I> {
+        match e {
+            Endianness::Little => self.read_int_le(),
+            Endianness::Big => self.read_int_be(),
+        }
+    }
+    read_int_endian!(
+        read_u16: u16,
+        read_u32: u32,
+        read_u64: u64,
+        read_u128: u128,
+        read_i16: i16,
+        read_i32: i32,
+        read_i64: i64,
+        read_i128: i128
+    );
+    read_int_ordered!(
+        read_u16_le: u16, Little, "little-endian";
+        read_u16_be: u16, Big, "big-endian";
+        read_u32_le: u32, Little, "little-endian";
+        read_u32_be: u32, Big, "big-endian";
+        read_u64_le: u64, Little, "little-endian";
+        read_u64_be: u64, Big, "big-endian";
+        read_u128_le: u128, Little, "little-endian";
+        read_u128_be: u128, Big, "big-endian";
+        read_i16_le: i16, Little, "little-endian";
+        read_i16_be: i16, Big, "big-endian";
+        read_i32_le: i32, Little, "little-endian";
+        read_i32_be: i32, Big, "big-endian";
+        read_i64_le: i64, Little, "little-endian";
+        read_i64_be: i64, Big, "big-endian";
+        read_i128_le: i128, Little, "little-endian";
+        read_i128_be: i128, Big, "big-endian";
+    );
+    /// Reads a little-endian `u16` and returns it as a `u8`. Function will fail without advancing if
+    /// there are not enough bytes left in the buffer, or if the value is more than `u8::MAX`.
+    pub fn read_u8_from_u16_le(&mut self) -> std::io::Result<u8> {
+        self.read_narrowed_le::<u16, u8>()
+    }
+    /// Reads a little-endian `u32` and returns it as a `u8`. Function will fail without advancing if
+    /// there are not enough bytes left in the buffer, or if the value is more than `u8::MAX`.
+    pub fn read_u8_from_u32_le(&mut self) -> std::io::Result<u8> {
+        self.read_narrowed_le::<u32, u8>()
+    }
+    /// Reads a little-endian `u32` and returns it as a `u16`. Function will fail without advancing
+    /// if there are not enough bytes left in the buffer, or if the value is more than `u16::MAX`.
+    pub fn read_u16_from_u32_le(&mut self) -> std::io::Result<u16> {
+        self.read_narrowed_le::<u32, u16>()
+    }
+    /// Reads a little-endian `u64` and returns it as a `u32`. Function will fail without advancing
+    /// if there are not enough bytes left in the buffer, or if the value is more than `u32::MAX`.
+    pub fn read_u32_from_u64_le(&mut self) -> std::io::Result<u32> {
+        self.read_narrowed_le::<u64, u32>()
+    }
+    /// Returns the next 4 bytes as a signed little-endian 16.16 fixed-point number, and then advances
+    /// the slice by 4. Function will fail if there are not enough bytes left in the buffer.
+    #[inline(always)]
+    pub fn read_fixed_16_16_le(&mut self) -> std::io::Result<f64> {
+        self.read_fixed(16, 16)
+    }
+    /// Returns the next `(int_bits + frac_bits) / 8` bytes as a signed little-endian fixed-point
+    /// number with `int_bits` integer bits (including the sign bit) and `frac_bits` fractional bits,
+    /// and then advances the slice past it. Function will fail if `int_bits + frac_bits` is not a
+    /// whole number of bytes between 1 and 8, or if there are not enough bytes left in the buffer.
+    pub fn read_fixed(&mut self, int_bits: u32, frac_bits: u32) -> std::io::Result<f64> {
+        let bits = int_bits
+            .checked_add(frac_bits)
+            .filter(|&bits| bits != 0 && bits <= 64 && bits.is_multiple_of(8))
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "BufferReader fixed-point width must be a whole number of bytes between 1 and 8",
+                )
+            })?;
+
+        let raw = self.read_signed_le(bits as usize / 8)?;
+        Ok(raw as f64 / 2f64.powi(frac_bits as i32))
+    }
+    /// Returns the next `byte_width` bytes as a little-endian two's complement signed integer, sign
+    /// extended to an `i64`, and then advances the slice by `byte_width`. Function will fail if
+    /// `byte_width` is not between 1 and 8, or if there are not enough bytes left in the buffer.
+    pub fn read_signed_le(&mut self, byte_width: usize) -> std::io::Result<i64> {
+        let signed = self.read_signed_bytes(byte_width)?;
+        let mut bytes = [0; 8];
+        bytes[..byte_width].copy_from_slice(signed);
+        Ok(Self::sign_extend(i64::from_le_bytes(bytes), byte_width))
+    }
+    /// Returns the next `byte_width` bytes as a big-endian two's complement signed integer, sign
+    /// extended to an `i64`, and then advances the slice by `byte_width`. Function will fail if
+    /// `byte_width` is not between 1 and 8, or if there are not enough bytes left in the buffer.
+    pub fn read_signed_be(&mut self, byte_width: usize) -> std::io::Result<i64> {
+        let signed = self.read_signed_bytes(byte_width)?;
+        let mut bytes = [0; 8];
+        bytes[8 - byte_width..].copy_from_slice(signed);
+        Ok(Self::sign_extend(i64::from_be_bytes(bytes), byte_width))
+    }
+    /// Returns the byte order of the file by checking the next 4 bytes against a known marker, without
+    /// advancing. If the bytes are `marker_le` in little-endian order, `Endianness::Little` is
+    /// returned, and if they are `marker_be` in big-endian order, `Endianness::Big` is returned.
+    /// Function will fail if there are not enough bytes left in the buffer, or if neither marker
+    /// matches.
+    pub fn detect_endianness(&self, marker_le: u32, marker_be: u32) -> std::io::Result<Endianness> {
+        let marker = *self.peek_t::<[u8; 4]>(0)?;
+        if marker == marker_le.to_le_bytes() {
+            Ok(Endianness::Little)
+        } else if marker == marker_be.to_be_bytes() {
+            Ok(Endianness::Big)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidData,
+                "BufferReader could not match either endianness marker",
+            ))
+        }
+    }
+    /// Reads a little-endian `u32` number of seconds since the Unix epoch, and returns it as a
+    /// `SystemTime`. Function will fail if there are not enough bytes left in the buffer.
+    pub fn read_unix_timestamp_secs_le(&mut self) -> std::io::Result<std::time::SystemTime> {
+        self.read_unix_timestamp(|br| {
+            Ok(std::time::Duration::from_secs(br.read_int_le::<u32>()? as u64))
+        })
+    }
+    /// Reads a little-endian `u64` number of milliseconds since the Unix epoch, and returns it as a
+    /// `SystemTime`. Function will fail without advancing if there are not enough bytes left in the
+    /// buffer, or if the time can't be represented by `SystemTime`.
+    pub fn read_unix_timestamp_millis_le(&mut self) -> std::io::Result<std::time::SystemTime> {
+        self.read_unix_timestamp(|br| {
+            Ok(std::time::Duration::from_millis(br.read_int_le::<u64>()?))
+        })
+    }
+    /// Returns the 4 bytes at `start` interpreted as both a little-endian and a big-endian `u32`, in
+    /// that order, without advancing. This is useful for sniffing a field of unknown byte order.
+    /// Function will fail if there are not enough bytes left in the buffer.
+    pub fn peek_u32_both(&self, start: usize) -> std::io::Result<(u32, u32)> {
+        let bytes = *self.peek_t::<[u8; 4]>(start)?;
+        Ok((u32::from_le_bytes(bytes), u32::from_be_bytes(bytes)))
+    }
+    /// Inflates the next `compressed_len` bytes of raw DEFLATE data, and then advances the slice past
+    /// the compressed bytes. Function will fail without advancing if there are not enough bytes left
+    /// in the buffer, if the data is not valid DEFLATE, or if the DEFLATE stream ends before
+    /// `compressed_len` bytes.
+    #[cfg(feature = "flate")]
+    pub fn read_deflate(&mut self, compressed_len: usize) -> std::io::Result<Vec<u8>> {
+        use std::io::Read;
+
+        let compressed = self.peek_bytes(0, compressed_len)?;
+        let mut decompressed = Vec::new();
+        let mut decoder = flate2::bufread::DeflateDecoder::new(compressed);
+        decoder.read_to_end(&mut decompressed)?;
+        let trailing = decoder.into_inner().len();
+        if trailing != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "BufferReader DEFLATE stream ended {} bytes before the compressed length",
+                    trailing,
+                ),
+            ));
+        }
+
+        self.advance(compressed_len);
+        Ok(decompressed)
+    }
+    /// Returns the first `bit_count` bits of the next `ceil(bit_count / 8)` bytes as booleans, and
+    /// then advances the slice past those bytes. Bits are read least significant bit first within
+    /// each byte, so bit 0 of the first byte is the first element. Function will fail if there are
+    /// not enough bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn read_bitset(&mut self, bit_count: usize) -> std::io::Result<Vec<bool>> {
+        let bytes = self.read_bytes(bit_count.div_ceil(8))?;
+        Ok((0..bit_count).map(|i| bytes[i / 8] >> (i % 8) & 1 != 0).collect())
+    }
+    /// Decodes a single GZIP member, returning the metadata from its header and the decompressed
+    /// payload, and then advances the slice past the end of the member's trailer. Function will fail
+    /// without advancing if the member is truncated, is not valid GZIP, or fails its CRC check.
+    #[cfg(feature = "flate")]
+    pub fn read_gzip_member(&mut self) -> std::io::Result<(GzipHeader, Vec<u8>)> {
+        use std::io::Read;
+
+        let mut decoder = flate2::bufread::GzDecoder::new(self.buffer);
+        let mut decompressed = Vec::new();
+        decoder.read_to_end(&mut decompressed)?;
+        let header = decoder.header().map(GzipHeader::from).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidData, "BufferReader GZIP header is missing")
+        })?;
+        let consumed = self.len() - decoder.into_inner().len();
+        self.advance(consumed);
+        Ok((header, decompressed))
+    }
+    /// Returns the value of the unsigned LEB128 encoded integer at the start of the buffer, and then
+    /// advances the slice past it. Function will fail if the buffer ends before the last byte of the
+    /// integer, or if the value does not fit in a `u64`.
+    pub fn read_uleb128(&mut self) -> std::io::Result<u64> {
+        let (value, len) = self.decode_uleb128()?;
+        self.advance(len);
+        Ok(value)
+    }
+    /// Returns the value of the unsigned LEB128 encoded integer at the start of the buffer and the
+    /// number of bytes it is encoded in, without advancing. Function will fail under the same
+    /// conditions as `read_uleb128`.
+    pub fn peek_uleb128(&self) -> std::io::Result<(u64, usize)> {
+        let (value, len) = self.decode_uleb128()?;
+        #[cfg(feature = "trace")]
+        self.record::<u64>(ReadKind::Peek, 0, len);
+        Ok((value, len))
+    }
+    /// Returns the value of the zigzag encoded ULEB128 integer at the start of the buffer, and then
+    /// advances the slice past it. Function will fail under the same conditions as `read_uleb128`.
+    pub fn read_zigzag_i64(&mut self) -> std::io::Result<i64> {
+        let n = self.read_uleb128()?;
+        Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+    }
+    /// Returns the value of the `byte_len` byte packed BCD number at the start of the buffer, and then
+    /// advances the slice past it. Each nibble is one decimal digit, most significant first. Function
+    /// will fail without advancing if a nibble is not a decimal digit, if the value does not fit in a
+    /// `u64`, or if there are not enough bytes left in the buffer.
+    pub fn read_bcd(&mut self, byte_len: usize) -> std::io::Result<u64> {
+        let mut value = 0u64;
+        for &byte in self.peek_bytes(0, byte_len)? {
+            for digit in [byte >> 4, byte & 0x0F] {
+                if digit > 9 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("BufferReader BCD byte {:#04X} has a nibble that is not a digit", byte),
+                    ));
+                }
+
+                value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit as u64))
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "BufferReader BCD number overflows a u64")
+                    })?;
+            }
+        }
+
+        self.advance(byte_len);
+        Ok(value)
+    }
+    /// Returns the value of the QUIC-style variable-length integer at the start of the buffer, and then
+    /// advances the slice past it. The top two bits of the first byte select the width of the value:
+    /// 0, 1, 2 and 3 mean a 1, 2, 4 or 8 byte big-endian value, with the two tag bits masked out.
+    /// Function will fail without advancing if there are not enough bytes left for the selected width.
+    pub fn read_tagged_length(&mut self) -> std::io::Result<u64> {
+        let width = 1 << (self.peek_byte(0)? >> 6);
+        let bytes = self.read_bytes(width)?;
+        let mut value = [0; 8];
+        value[8 - width..].copy_from_slice(bytes);
+        value[8 - width] &= 0x3F;
+        Ok(u64::from_be_bytes(value))
+    }
+    /// Returns the value of the big-endian variable-length quantity (as used by MIDI) at the start of
+    /// the buffer, and then advances the slice past it. Each byte holds 7 bits of the value, most
+    /// significant first, with the high bit set on every byte but the last. Function will fail
+    /// without advancing if the buffer ends before the last byte, or if the value does not fit in a
+    /// `u32`.
+    pub fn read_vlq(&mut self) -> std::io::Result<u32> {
+        let mut value = 0u32;
+        for (i, &byte) in self.buffer.iter().enumerate() {
+            if value > u32::MAX >> 7 {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "BufferReader variable-length quantity overflows a u32",
+                ));
+            }
+
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                self.advance(i + 1);
+                return Ok(value);
+            }
+        }
+
+        Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "BufferReader variable-length quantity is truncated",
+        ))
+    }
+    /// Returns `count` absolute values reconstructed from a sequence of zigzag encoded deltas, and
+    /// then advances the slice past the deltas. The first delta is applied to `initial`, and each
+    /// following delta is applied to the previous value, wrapping on overflow. Function will fail
+    /// without advancing if any of the deltas cannot be read.
+    #[cfg(feature = "alloc")]
+    pub fn read_delta_sequence_i64(&mut self, count: usize, initial: i64) -> std::io::Result<Vec<i64>> {
+        self.transaction(|br| {
+            let mut values = Vec::with_capacity(count.min(br.len()));
+            let mut value = initial;
+            for _ in 0..count {
+                value = value.wrapping_add(br.read_zigzag_i64()?);
+                values.push(value);
+            }
+
+            Ok(values)
+        })
+    }
+    /// Advances the slice past `count` bytes of padding, checking that every byte is `expected`.
+    /// Function will fail without advancing if there are not enough bytes left in the buffer, or if
+    /// any of the padding bytes is not `expected`.
+    pub fn skip_padding(&mut self, count: usize, expected: u8) -> std::io::Result<()> {
+        self.check_available(count)?;
+        if let Some(i) = self.buffer[..count].iter().position(|&b| b != expected) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "BufferReader expected padding byte {:#04X} at offset {:#X}, found {:#04X}",
+                    expected,
+                    self.position() + i,
+                    self.buffer[i],
+                ),
+            ));
+        }
+
+        self.advance(count);
+        Ok(())
+    }
+    /// Checks that every remaining byte is `byte`, such as the fill at the end of a fixed-size record,
+    /// and then advances the slice to the end. This is the same as `skip_padding(self.len(), byte)`.
+    /// Function will fail without advancing if any remaining byte is not `byte`.
+    #[inline(always)]
+    pub fn expect_remaining_all(&mut self, byte: u8) -> std::io::Result<()> {
+        self.skip_padding(self.len(), byte)
+    }
     /// Returns the value of the next byte and advances the slice by one. Function will fail if the
     /// length of the underlying slice is less than 1.
     /// If you want a reference to the byte, use `read_t`
@@ -61,16 +1096,42 @@ impl<'a> BufferReader<'a> {
         self.check_available(std::mem::size_of::<u8>())?;
         // SAFETY: advance returns a slice with the number of bytes we read, so, we return the only
         // byte in the slice.
-        Ok(self.advance(std::mem::size_of::<u8>())[0])
+        Ok(self.advance_as::<u8>(std::mem::size_of::<u8>())[0])
+    }
+    /// Returns the next byte converted to `E` with `TryFrom<u8>`, and then advances the slice by one.
+    /// This works well with enums that derive `TryFromPrimitive`. Function will fail without
+    /// advancing if the buffer is empty, or if the byte is not a valid `E`.
+    pub fn read_enum_u8<E: TryFrom<u8>>(&mut self) -> std::io::Result<E> {
+        let byte = self.peek_byte(0)?;
+        let e = E::try_from(byte).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "BufferReader byte {:#04X} is not a valid `{}`",
+                    byte,
+                    std::any::type_name::<E>(),
+                ),
+            )
+        })?;
+        self.advance_as::<u8>(std::mem::size_of::<u8>());
+        Ok(e)
     }
     /// Returns the value of the next byte. Function will fail if the length of the underlying slice
     /// is less than 1.
     /// If you want a reference to the byte, use `peek_t`
     pub fn peek_byte(&self, pos: usize) -> std::io::Result<u8> {
-        self.check_available(std::mem::size_of::<u8>())?;
+        self.check_available(pos.saturating_add(std::mem::size_of::<u8>()))?;
+        #[cfg(feature = "trace")]
+        self.record::<u8>(ReadKind::Peek, pos, std::mem::size_of::<u8>());
         // SAFETY: see read_byte
         Ok(self.peek_remaining()[pos])
     }
+    /// Returns the value of the byte at `pos`, or `None` if `pos` is past the end of the buffer. This
+    /// never fails or panics, which makes it handy for lookahead at the end of the buffer.
+    #[inline(always)]
+    pub fn peek_byte_opt(&self, pos: usize) -> Option<u8> {
+        self.buffer.get(pos).copied()
+    }
     /// Returns a reference to the next `n` bytes specified by the `len` parameter and advances the
     /// underlying slice by `len`. Function will fail if the length of the underlying slice is less
     /// than the size provided.
@@ -78,77 +1139,781 @@ impl<'a> BufferReader<'a> {
         self.check_available(len)?;
         Ok(self.advance(len))
     }
+    /// Same as `read_bytes`, but does not check that there are enough bytes left in the buffer. This
+    /// is for hot loops where the total length has already been validated.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that there are at least `len` bytes left in the buffer.
+    pub unsafe fn read_bytes_unchecked(&mut self, len: usize) -> &'a [u8] {
+        // SAFETY: The caller guarantees there is room in the buffer.
+        unsafe { self.advance_unchecked::<[u8]>(len) }
+    }
     /// Returns a reference to the next `n` bytes specified by the `len` parameter. Function will fail
     /// if the length of the underlying slice is less than the size provided.
     pub fn peek_bytes(&self, start: usize, len: usize) -> std::io::Result<&'a [u8]> {
-        let end = start + len;
+        let end = start.saturating_add(len);
         self.check_available(end)?;
+        #[cfg(feature = "trace")]
+        self.record::<[u8]>(ReadKind::Peek, start, len);
         Ok(&self.peek_remaining()[start..end])
     }
-    /// Returns the length of the remaining buffer.
-    #[inline(always)]
-    pub fn len(&self) -> usize {
-        self.buffer.len()
+    /// Appends the next `len` bytes to `out`, and then advances the slice by `len`. This avoids a new
+    /// allocation for each read when building up a result in a loop. Function will fail without
+    /// touching `out` if there are not enough bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn read_bytes_append(&mut self, len: usize, out: &mut Vec<u8>) -> std::io::Result<()> {
+        out.extend_from_slice(self.read_bytes(len)?);
+        Ok(())
     }
-    /// Returns true of the inner buffer is empty.
-    #[inline(always)]
-    pub fn is_empty(&self) -> bool {
-        self.buffer.is_empty()
+    /// Returns an owned copy of the next `len` bytes in reverse order, and then advances the slice
+    /// by `len`. Function will fail if there are not enough bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn read_bytes_reversed(&mut self, len: usize) -> std::io::Result<Vec<u8>> {
+        Ok(self.read_bytes(len)?.iter().rev().copied().collect())
     }
-    /// Returns a reference to the remaining bytes in the slice.
-    #[inline(always)]
-    pub fn peek_remaining(&self) -> &'a [u8] {
-        self.buffer
+    /// Returns an owned copy of the `len` bytes at `start` in reverse order. Function will fail if
+    /// there are not enough bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn peek_bytes_reversed(&self, start: usize, len: usize) -> std::io::Result<Vec<u8>> {
+        Ok(self.peek_bytes(start, len)?.iter().rev().copied().collect())
     }
-    /// Returns a reference to the remaining bytes in the slice.
-    #[inline(always)]
-    pub fn get_remaining(self) -> &'a [u8] {
-        self.buffer
+    /// Returns an owned `Vec` of `count` elements of `element_size` bytes each, where the start of each
+    /// element is `stride` bytes after the start of the previous one, and then advances the slice by
+    /// `count` * `stride` bytes. The bytes between elements are skipped, so this can be used to pull
+    /// one channel out of interleaved data. Function will fail without advancing if `stride` is less
+    /// than `element_size`, or if there are fewer than `count` * `stride` bytes left in the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn read_strided_bytes(
+        &mut self,
+        element_size: usize,
+        stride: usize,
+        count: usize,
+    ) -> std::io::Result<Vec<u8>> {
+        if stride < element_size {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "BufferReader stride is smaller than the element size",
+            ));
+        }
+
+        let size = Self::elements_size(stride, count)?;
+        self.check_available(size)?;
+        Ok(self
+            .advance(size)
+            .chunks_exact(stride.max(1))
+            .flat_map(|element| &element[..element_size])
+            .copied()
+            .collect())
     }
-    /// Returns the position of the pattern of bytes provided, or `None` if the pattern is not found.
-    pub fn find_bytes(&self, pat: &[u8]) -> Option<usize> {
-        let buffer = self.buffer;
-        let pat_len = pat.len();
+    /// Returns all of the remaining bytes split into UTF-8 lines, and then advances the slice to the
+    /// end. Lines end with `\n` or `\r\n`, which are not part of the returned lines, and a line
+    /// ending at the very end of the buffer does not start another line. Function will fail without
+    /// advancing if any line is not valid UTF-8.
+    #[cfg(feature = "alloc")]
+    pub fn read_remaining_lines(&mut self) -> std::io::Result<Vec<&'a str>> {
+        let remaining = self.buffer;
+        if remaining.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        let mut i = 0;
-        while i < buffer.len() - (pat_len - 1) {
-            if &buffer[i..pat_len + i] == pat {
-                return Some(i);
+        let lines = remaining
+            .strip_suffix(b"\n")
+            .unwrap_or(remaining)
+            .split(|&b| b == b'\n')
+            .map(|line| {
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                std::str::from_utf8(line).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+            })
+            .collect::<std::io::Result<_>>()?;
+        self.advance(remaining.len());
+        Ok(lines)
+    }
+    /// Returns a reference to the bytes of a string stored with the encoding `enc`, not including any
+    /// length prefix, terminator or padding, and then advances the slice past the whole field.
+    /// Function will fail without advancing if the field runs past the end of the buffer.
+    pub fn read_string(&mut self, enc: StringEncoding) -> std::io::Result<&'a [u8]> {
+        match enc {
+            StringEncoding::FixedNulPadded(len) => {
+                let bytes = self.read_bytes(len)?;
+                Ok(bytes.split(|&b| b == 0).next().unwrap_or(bytes))
             }
-
-            i += 1;
+            StringEncoding::LenPrefixU8 => self.read_pascal_str(),
+            StringEncoding::LenPrefixU16Le => self.transaction(|br| {
+                let len = br.read_int_le::<u16>()?;
+                br.read_bytes(len as usize)
+            }),
+            StringEncoding::CStr => self.read_cstr(),
         }
-
-        None
     }
-    /// Advance the start of the buffer by the number of bytes provided by `len`. Returns a slice from
-    /// the previous start of the buffer up until the new start of the buffer.
-    ///
-    /// # Safety
-    ///
-    /// Caller should call `self.check_available(size)` before calling this to check if there is room
-    /// in the buffer to advance.
-    #[inline(always)]
-    fn advance(&mut self, len: usize) -> &'a [u8] {
-        let buffer = self.buffer;
-        self.buffer = &buffer[len..];
-        &buffer[..len]
+    /// Returns a reference to the bytes of a NUL terminated string, not including the NUL, and then
+    /// advances the slice past the NUL. Function will fail without advancing if there is no NUL left
+    /// in the buffer.
+    pub fn read_cstr(&mut self) -> std::io::Result<&'a [u8]> {
+        self.read_cstr_max(self.len())
     }
-    /// Checks if there are enough bytes left in the buffer.
-    fn check_available(&self, len: usize) -> std::io::Result<()> {
-        if len > self.buffer.len() {
-            return Err(Error::new(
+    /// Same as `read_cstr`, but only searches the next `max_len` bytes for the NUL, so a buffer with no
+    /// NUL is not scanned to the end. Function will fail without advancing if there is no NUL within
+    /// the next `max_len` bytes.
+    pub fn read_cstr_max(&mut self, max_len: usize) -> std::io::Result<&'a [u8]> {
+        let window = &self.buffer[..max_len.min(self.len())];
+        let len = window.iter().position(|&b| b == 0).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("BufferReader could not find a NUL within {} bytes", window.len()),
+            )
+        })?;
+        let bytes = self.advance(len + 1);
+        Ok(&bytes[..len])
+    }
+    /// Returns a reference to the bytes up to the next `\r\n`, not including it, and then advances the
+    /// slice past the `\r\n`. A lone `\n` does not end the line. Function will fail without
+    /// advancing if there is no `\r\n` left in the buffer.
+    pub fn read_line_crlf(&mut self) -> std::io::Result<&'a [u8]> {
+        let len = self.find_bytes(b"\r\n").ok_or_else(|| {
+            Error::new(
                 ErrorKind::UnexpectedEof,
-                "BufferReader advance would result in an index that is out of bounds",
-            ));
-        }
-
-        Ok(())
+                "BufferReader could not find a CRLF in the remaining buffer",
+            )
+        })?;
+        let bytes = self.advance(len + 2);
+        Ok(&bytes[..len])
     }
-}
-
-#[cfg(feature = "read")]
-use std::io::Read;
+    /// Same as `read_line_crlf`, but a line can also end with a bare `\n`. The line ending, including
+    /// the `\r` of a `\r\n`, is not part of the returned line. Function will fail without advancing
+    /// if there is no `\n` left in the buffer.
+    pub fn read_line(&mut self) -> std::io::Result<&'a [u8]> {
+        let len = self.find_bytes(b"\n").ok_or_else(|| {
+            Error::new(
+                ErrorKind::UnexpectedEof,
+                "BufferReader could not find a line ending in the remaining buffer",
+            )
+        })?;
+        let bytes = self.advance(len + 1);
+        let line = &bytes[..len];
+        Ok(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+    /// Returns a reference to the bytes of a Pascal string, which is a single length byte followed by
+    /// that many bytes, and then advances the slice past both. Function will fail without advancing
+    /// if the length is more than the number of bytes left in the buffer.
+    pub fn read_pascal_str(&mut self) -> std::io::Result<&'a [u8]> {
+        let len = self.peek_byte(0)? as usize;
+        let bytes = self.peek_bytes(1, len)?;
+        self.advance(1 + len);
+        Ok(bytes)
+    }
+    /// Same as `read_pascal_str`, but also checks that the string is valid UTF-8. This is the same as
+    /// `read_len_prefixed_str_u8`.
+    #[inline(always)]
+    pub fn read_pascal_str_str(&mut self) -> std::io::Result<&'a str> {
+        self.read_len_prefixed_str_u8()
+    }
+    /// Returns a reference to a UTF-8 string prefixed by its length in bytes as a `u8`, and then
+    /// advances the slice past both. Function will fail without advancing if there are not enough
+    /// bytes left in the buffer, or if the string is not valid UTF-8.
+    pub fn read_len_prefixed_str_u8(&mut self) -> std::io::Result<&'a str> {
+        self.read_len_prefixed_str(|len: u8| len as usize)
+    }
+    /// Returns a reference to a UTF-8 string prefixed by its length in bytes as a little-endian
+    /// `u16`, and then advances the slice past both. Function will fail without advancing if there
+    /// are not enough bytes left in the buffer, or if the string is not valid UTF-8.
+    pub fn read_len_prefixed_str_u16_le(&mut self) -> std::io::Result<&'a str> {
+        self.read_len_prefixed_str(|len: [u8; 2]| u16::from_le_bytes(len) as usize)
+    }
+    /// Returns a reference to a UTF-8 string prefixed by its length in bytes as a little-endian
+    /// `u32`, and then advances the slice past both. Function will fail without advancing if there
+    /// are not enough bytes left in the buffer, or if the string is not valid UTF-8.
+    pub fn read_len_prefixed_str_u32_le(&mut self) -> std::io::Result<&'a str> {
+        self.read_len_prefixed_str(|len: [u8; 4]| u32::from_le_bytes(len) as usize)
+    }
+    /// Copies bytes from the buffer into each of the provided buffers in order, and then advances
+    /// the slice by the total number of bytes copied, which is returned. This stops early if the
+    /// reader runs out of bytes, and never fails.
+    ///
+    /// # Warning - will copy bytes to provided buffers
+    pub fn read_vectored_into(&mut self, bufs: &mut [IoSliceMut]) -> std::io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs {
+            let len = buf.len().min(self.len());
+            buf[..len].copy_from_slice(self.advance(len));
+            total += len;
+        }
+
+        Ok(total)
+    }
+    /// Reads a little-endian `u32` version field, and then passes the version and this reader to
+    /// `parse`, which reads the body for that version. If the version can't be read or `parse`
+    /// fails, the reader is restored to where it was before the version field.
+    pub fn read_versioned<T>(
+        &mut self,
+        parse: impl FnOnce(u32, &mut BufferReader<'a>) -> std::io::Result<T>,
+    ) -> std::io::Result<T> {
+        self.transaction(|br| {
+            let version = br.read_int_le::<u32>()?;
+            parse(version, br)
+        })
+    }
+    /// Reads a one byte tag, and then passes the tag and this reader to `dispatch`, which reads the body
+    /// of the variant the tag selects. If the tag can't be read or `dispatch` fails, the reader is
+    /// restored to where it was before the tag.
+    pub fn read_union<T>(
+        &mut self,
+        dispatch: impl FnOnce(u8, &mut BufferReader<'a>) -> std::io::Result<T>,
+    ) -> std::io::Result<T> {
+        self.transaction(|br| {
+            let tag = br.read_byte()?;
+            dispatch(tag, br)
+        })
+    }
+    /// Returns a guard holding a child reader over the next `record_len` bytes. When the guard is
+    /// dropped, this reader is advanced by exactly `record_len`, even if the child did not read the
+    /// whole record. Function will fail if there are fewer than `record_len` bytes left.
+    pub fn scoped(&mut self, record_len: usize) -> std::io::Result<RecordScope<'_, 'a>> {
+        RecordScope::new(self, record_len)
+    }
+    /// Runs `f` on this reader, keeping any reads it made if it succeeds. If `f` fails, the reader is
+    /// restored to where it was before `f` was called, so a multi-step parse either fully succeeds
+    /// or leaves the reader untouched.
+    pub fn transaction<T>(
+        &mut self,
+        f: impl FnOnce(&mut BufferReader<'a>) -> std::io::Result<T>,
+    ) -> std::io::Result<T> {
+        let (buffer, end) = (self.buffer, self.end);
+        let result = f(self);
+        if result.is_err() {
+            self.buffer = buffer;
+            self.end = end;
+        }
+
+        result
+    }
+    /// Returns the state of the reader, which can be saved and passed to `restore_state` to rebuild a
+    /// reader at the same position.
+    #[inline(always)]
+    pub fn state(&self) -> ReaderState {
+        ReaderState {
+            position: self.position(),
+        }
+    }
+    /// Returns a checkpoint of the current position, which can be passed to `restore` to rewind the
+    /// reader back to it.
+    #[inline(always)]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.position(),
+        }
+    }
+    /// Moves the reader back (or forward) to the position saved in `cp`. The checkpoint should come
+    /// from this reader; a position past the end of the buffer leaves the reader empty.
+    pub fn restore(&mut self, cp: Checkpoint) {
+        self.buffer = &self.base[cp.position.min(self.end)..self.end];
+    }
+    /// Returns an independent reader at the current position, over the same slice. This is useful for
+    /// speculative parsing: parse on the fork, and if the parse succeeds, move this reader up to it
+    /// with `set_position(fork.position())`. If the parse fails, just drop the fork.
+    pub fn fork(&self) -> BufferReader<'a> {
+        BufferReader {
+            base: self.base,
+            buffer: self.buffer,
+            end: self.end,
+            #[cfg(feature = "trace")]
+            trace: Default::default(),
+        }
+    }
+    /// Moves the reader to the absolute `position` in the original slice, either forwards or
+    /// backwards. Function will fail without moving if `position` is past the end of the buffer.
+    pub fn set_position(&mut self, position: usize) -> std::io::Result<()> {
+        if position > self.end {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "BufferReader position {:#X} is past the end of the buffer ({:#X})",
+                    position, self.end,
+                ),
+            ));
+        }
+
+        self.buffer = &self.base[position..self.end];
+        Ok(())
+    }
+    /// Moves the reader back by `len` bytes, so they can be read again. Function will fail without
+    /// moving if `len` is more than the number of bytes that have been read.
+    pub fn rewind(&mut self, len: usize) -> std::io::Result<()> {
+        let position = self.position().checked_sub(len).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "BufferReader can't rewind {} bytes from offset {:#X}",
+                    len,
+                    self.position(),
+                ),
+            )
+        })?;
+        self.buffer = &self.base[position..self.end];
+        Ok(())
+    }
+    /// Returns the 64-bit FNV-1a hash of the bytes consumed since `cp` was taken. If the reader is
+    /// not past the checkpoint, this is the hash of no bytes.
+    pub fn fnv1a_since(&self, cp: Checkpoint) -> u64 {
+        let consumed = self.base.get(cp.position..self.position()).unwrap_or_default();
+        consumed.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &b| {
+            (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01B3)
+        })
+    }
+    /// Checks that there are at least `len` bytes left in the buffer, so a sequence of reads can fail
+    /// up front instead of partway through. Function will fail if there are fewer than `len` bytes
+    /// left.
+    #[inline(always)]
+    pub fn require(&self, len: usize) -> std::io::Result<()> {
+        self.check_available(len)
+    }
+    /// Returns the number of bytes remaining in the buffer. This shrinks as the reader advances, and
+    /// is not the length of the original slice.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+    /// Returns true if there are no bytes remaining in the buffer.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+    /// Returns the number of bytes remaining in the buffer. This is the same as `len`.
+    #[inline(always)]
+    pub fn bytes_available(&self) -> usize {
+        self.len()
+    }
+    /// Returns true if the cursor is aligned for `T`, so `read_t` and `read_slice_t` can borrow `T`
+    /// directly from the buffer. If it isn't, copy the values out with `read_vec_t` instead.
+    #[inline(always)]
+    pub fn is_aligned_for<T>(&self) -> bool {
+        (self.buffer.as_ptr() as usize).is_multiple_of(std::mem::align_of::<T>())
+    }
+    /// Returns a copy of every read and peek this reader has done, in order. This is useful for
+    /// seeing exactly which parts of a buffer a parser touched. The events are recorded behind a
+    /// lock, so enabling the `trace` feature does not change whether the reader is `Send` or `Sync`.
+    #[cfg(feature = "trace")]
+    pub fn trace(&self) -> Vec<ReadEvent> {
+        self.trace_events().clone()
+    }
+    /// Same as `trace`, but also clears the recorded events, so the next call only returns the reads
+    /// and peeks done after this one.
+    #[cfg(feature = "trace")]
+    pub fn take_trace(&self) -> Vec<ReadEvent> {
+        std::mem::take(&mut *self.trace_events())
+    }
+    /// Returns the number of whole `T` elements that fit in the remaining buffer. If `T` is a zero
+    /// sized type, any number of them fit, so this returns `usize::MAX`.
+    #[inline(always)]
+    pub fn remaining_count<T>(&self) -> usize {
+        match std::mem::size_of::<T>() {
+            0 => usize::MAX,
+            size => self.buffer.len() / size,
+        }
+    }
+    /// Returns the number of bytes that have been read since the reader was created, which is the
+    /// absolute offset of the cursor into the original slice.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.end - self.buffer.len()
+    }
+    /// Returns the absolute offset into the original slice of a position `relative` bytes past the
+    /// cursor, or `None` if the offset would overflow a `usize`.
+    #[inline(always)]
+    pub fn abs_offset(&self, relative: usize) -> Option<usize> {
+        self.position().checked_add(relative)
+    }
+    /// Returns the position relative to the cursor of an `absolute` offset into the original slice.
+    /// Function will fail if `absolute` is behind the cursor or past the end of the remaining buffer.
+    pub fn rel_offset(&self, absolute: usize) -> std::io::Result<usize> {
+        if absolute < self.position() || absolute > self.end {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "BufferReader offset {:#X} is outside of the remaining buffer ({:#X}..{:#X})",
+                    absolute,
+                    self.position(),
+                    self.end,
+                ),
+            ));
+        }
+
+        Ok(absolute - self.position())
+    }
+    /// Reserves the last `footer_len` bytes of the original slice as a footer, so every read treats
+    /// the buffer as if it ends `footer_len` bytes early. The footer can still be read with
+    /// `read_footer`. Calling this again replaces the previous reservation. Function will fail if the
+    /// footer would overlap bytes that have already been read.
+    pub fn set_footer_reserve(&mut self, footer_len: usize) -> std::io::Result<()> {
+        let position = self.position();
+        if footer_len > self.base.len() - position {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "BufferReader footer of {} bytes overlaps the cursor at offset {:#X}",
+                    footer_len, position,
+                ),
+            ));
+        }
+
+        self.end = self.base.len() - footer_len;
+        self.buffer = &self.base[position..self.end];
+        Ok(())
+    }
+    /// Returns a reference to the footer reserved by `set_footer_reserve`, which is empty if no footer
+    /// has been reserved. Does not advance the slice.
+    #[inline(always)]
+    pub fn read_footer(&self) -> &'a [u8] {
+        &self.base[self.end..]
+    }
+    /// Returns a reference to the remaining bytes in the slice.
+    #[inline(always)]
+    pub fn peek_remaining(&self) -> &'a [u8] {
+        self.buffer
+    }
+    /// Returns a reference to the remaining bytes in the slice.
+    #[inline(always)]
+    pub fn get_remaining(self) -> &'a [u8] {
+        self.buffer
+    }
+    /// Returns a reference to the remaining bytes in the slice, and then advances the slice to the
+    /// end, so any further reads will fail. Unlike `get_remaining`, this keeps the reader around.
+    #[inline(always)]
+    pub fn read_remaining(&mut self) -> &'a [u8] {
+        self.advance(self.len())
+    }
+    /// Returns an owned copy of the remaining bytes in this reader, followed by the remaining bytes
+    /// in `other`. Neither reader is advanced.
+    #[cfg(feature = "alloc")]
+    pub fn concat_remaining(&self, other: &BufferReader) -> Vec<u8> {
+        [self.buffer, other.buffer].concat()
+    }
+    /// Returns an iterator over the remaining bytes, paired with their absolute offset into the
+    /// original slice. Does not advance the slice.
+    pub fn enumerate_bytes(&self) -> impl Iterator<Item = (usize, u8)> + 'a {
+        let position = self.position();
+        self.buffer
+            .iter()
+            .enumerate()
+            .map(move |(i, &b)| (position + i, b))
+    }
+    /// Returns true if the bytes at `offset` in the remaining buffer are equal to `expected`. Returns
+    /// false if the range is out of bounds, and never panics.
+    pub fn matches_at(&self, offset: usize, expected: &[u8]) -> bool {
+        offset
+            .checked_add(expected.len())
+            .and_then(|end| self.buffer.get(offset..end))
+            .is_some_and(|bytes| bytes == expected)
+    }
+    /// Returns an iterator over the sub-slices of the remaining buffer separated by `delim`, like
+    /// `slice::split`. Does not advance the slice.
+    pub fn split_remaining(&self, delim: u8) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.buffer.split(move |&b| b == delim)
+    }
+    /// Returns the offset of the first `T` in the remaining buffer for which `pred` returns true, or
+    /// `None` if there isn't one. The buffer is scanned as a sequence of `T` from the current
+    /// position, so the offset is always a multiple of the size of `T`. Does not advance the slice.
+    pub fn find_t<T: AnyBitPattern>(&self, pred: impl Fn(&T) -> bool) -> Option<usize> {
+        let size = std::mem::size_of::<T>();
+        if size == 0 {
+            return pred(&T::zeroed()).then_some(0);
+        }
+
+        self.buffer
+            .chunks_exact(size)
+            .position(|t| pred(&bytemuck::pod_read_unaligned(t)))
+            .map(|i| i * size)
+    }
+    /// Returns the XOR of the next `len` bytes, without advancing. This is useful for checking a region
+    /// against a simple parity checksum before reading it. Function will fail if there are not enough
+    /// bytes left in the buffer.
+    pub fn peek_checksum_u8(&self, len: usize) -> std::io::Result<u8> {
+        Ok(self.peek_bytes(0, len)?.iter().fold(0, |sum, &b| sum ^ b))
+    }
+    /// Same as `peek_checksum_u8`, but returns the wrapping sum of the bytes instead of their XOR.
+    pub fn peek_checksum_sum_u8(&self, len: usize) -> std::io::Result<u8> {
+        Ok(self.peek_bytes(0, len)?.iter().fold(0, |sum: u8, &b| sum.wrapping_add(b)))
+    }
+    /// Returns the number of times `byte` appears in the remaining buffer. Does not advance the slice.
+    pub fn count_byte(&self, byte: u8) -> usize {
+        self.buffer.iter().filter(|&&b| b == byte).count()
+    }
+    /// Returns the position of the pattern of bytes provided, or `None` if the pattern is not found.
+    /// An empty pattern is always found at position 0, and a pattern longer than the remaining buffer
+    /// is never found.
+    pub fn find_bytes(&self, pat: &[u8]) -> Option<usize> {
+        if pat.is_empty() {
+            return Some(0);
+        }
+
+        self.buffer.windows(pat.len()).position(|window| window == pat)
+    }
+    /// Returns the number of bytes from the cursor to the start of the next occurrence of the pattern
+    /// provided, or `None` if the pattern is not found. This is the same as `find_bytes`, but reads
+    /// better when sizing a record. Does not advance the slice.
+    #[inline(always)]
+    pub fn distance_to_bytes(&self, pat: &[u8]) -> Option<usize> {
+        self.find_bytes(pat)
+    }
+    /// Returns a reference to the bytes immediately following the first occurrence of the pattern
+    /// provided, or `None` if the pattern is not found. Does not advance the slice.
+    pub fn after_bytes(&self, pat: &[u8]) -> Option<&'a [u8]> {
+        let pos = self.find_bytes(pat)?;
+        Some(&self.buffer[pos + pat.len()..])
+    }
+    /// Advances the slice to just past the first occurrence of the pattern provided, and returns the
+    /// number of bytes advanced, including the pattern. Returns `None` without advancing if the
+    /// pattern is not found.
+    pub fn seek_after_bytes(&mut self, pat: &[u8]) -> Option<usize> {
+        let len = self.find_bytes(pat)? + pat.len();
+        self.advance(len);
+        Some(len)
+    }
+    /// Advances the slice to the start of the first occurrence of the pattern provided, and returns
+    /// the number of bytes discarded. This is useful for resynchronizing a stream after a corrupt
+    /// region. Function will fail without advancing if the pattern is not found.
+    pub fn discard_until_bytes(&mut self, pat: &[u8]) -> std::io::Result<usize> {
+        let len = self.find_bytes(pat).ok_or_else(|| {
+            Error::new(
+                ErrorKind::UnexpectedEof,
+                "BufferReader could not find the pattern in the remaining buffer",
+            )
+        })?;
+        self.advance(len);
+        Ok(len)
+    }
+    /// Advance the start of the buffer by the number of bytes provided by `len`. Returns a slice from
+    /// the previous start of the buffer up until the new start of the buffer.
+    ///
+    /// # Safety
+    ///
+    /// Caller should call `self.check_available(size)` before calling this to check if there is room
+    /// in the buffer to advance.
+    #[inline(always)]
+    fn advance(&mut self, len: usize) -> &'a [u8] {
+        self.advance_as::<[u8]>(len)
+    }
+    /// Same as `advance`, but records the read as a read of `T` when the `trace` feature is enabled.
+    #[inline(always)]
+    #[cfg_attr(not(feature = "trace"), allow(clippy::extra_unused_type_parameters))]
+    fn advance_as<T: ?Sized>(&mut self, len: usize) -> &'a [u8] {
+        #[cfg(feature = "trace")]
+        self.record::<T>(ReadKind::Read, 0, len);
+        let buffer = self.buffer;
+        self.buffer = &buffer[len..];
+        &buffer[..len]
+    }
+    /// Same as `advance_as`, but without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that there are at least `len` bytes left in the buffer.
+    #[inline(always)]
+    #[cfg_attr(not(feature = "trace"), allow(clippy::extra_unused_type_parameters))]
+    unsafe fn advance_unchecked<T: ?Sized>(&mut self, len: usize) -> &'a [u8] {
+        #[cfg(feature = "trace")]
+        self.record::<T>(ReadKind::Read, 0, len);
+        let buffer = self.buffer;
+        // SAFETY: The caller guarantees that `len` is in bounds.
+        unsafe {
+            self.buffer = buffer.get_unchecked(len..);
+            buffer.get_unchecked(..len)
+        }
+    }
+    /// Records an operation on `len` bytes of `T`, starting `start` bytes past the cursor.
+    #[cfg(feature = "trace")]
+    fn record<T: ?Sized>(&self, kind: ReadKind, start: usize, len: usize) {
+        let event = ReadEvent {
+            offset: self.position() + start,
+            len,
+            kind,
+            type_name: std::any::type_name::<T>(),
+        };
+        self.trace_events().push(event);
+    }
+    /// Locks the recorded trace events. The lock is never held across a call that could panic, so a
+    /// poisoned lock still holds a valid list of events.
+    #[cfg(feature = "trace")]
+    fn trace_events(&self) -> std::sync::MutexGuard<'_, Vec<ReadEvent>> {
+        self.trace.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+    /// Reads a length prefix of type `L`, converts it to a byte count with `to_len`, and returns the
+    /// UTF-8 string that follows it. Only advances if the whole string was read successfully.
+    fn read_len_prefixed_str<L: AnyBitPattern>(
+        &mut self,
+        to_len: impl FnOnce(L) -> usize,
+    ) -> std::io::Result<&'a str> {
+        let prefix_len = std::mem::size_of::<L>();
+        let len = to_len(bytemuck::pod_read_unaligned(self.peek_bytes(0, prefix_len)?));
+        let bytes = self.peek_bytes(prefix_len, len)?;
+        let s = std::str::from_utf8(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        self.advance(prefix_len + len);
+        Ok(s)
+    }
+    /// Decodes the unsigned LEB128 integer at the start of the buffer, returning the value and the
+    /// number of bytes it was encoded in.
+    fn decode_uleb128(&self) -> std::io::Result<(u64, usize)> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        for (i, &byte) in self.buffer.iter().enumerate() {
+            let bits = (byte & 0x7F) as u64;
+            if shift >= 64 || (shift == 63 && bits > 1) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "BufferReader LEB128 integer overflows a u64",
+                ));
+            }
+
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value, i + 1));
+            }
+
+            shift += 7;
+        }
+
+        Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "BufferReader LEB128 integer is truncated",
+        ))
+    }
+    /// Reads a little-endian `W` and narrows it to an `N`. Only advances if the value fits in an `N`.
+    fn read_narrowed_le<W, N>(&mut self) -> std::io::Result<N>
+    where
+        W: IntFromBytes + std::fmt::Display,
+        N: TryFrom<W>,
+    {
+        self.transaction(|br| {
+            let wide = br.read_int_le::<W>()?;
+            N::try_from(wide).map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "BufferReader value {} does not fit in a `{}`",
+                        wide,
+                        std::any::type_name::<N>(),
+                    ),
+                )
+            })
+        })
+    }
+    /// Reads a time since the Unix epoch with `read`, and converts it to a `SystemTime`. Only advances if
+    /// the time can be represented.
+    fn read_unix_timestamp(
+        &mut self,
+        read: impl FnOnce(&mut BufferReader<'a>) -> std::io::Result<std::time::Duration>,
+    ) -> std::io::Result<std::time::SystemTime> {
+        self.transaction(|br| {
+            std::time::UNIX_EPOCH.checked_add(read(br)?).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    "BufferReader timestamp can't be represented by SystemTime",
+                )
+            })
+        })
+    }
+    /// Reads the bytes of a signed integer `byte_width` bytes wide, checking the width is 1 to 8.
+    fn read_signed_bytes(&mut self, byte_width: usize) -> std::io::Result<&'a [u8]> {
+        if !(1..=8).contains(&byte_width) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "BufferReader signed integer width must be between 1 and 8 bytes",
+            ));
+        }
+
+        self.read_bytes(byte_width)
+    }
+    /// Sign extends an integer `byte_width` bytes wide, held in the low bytes of `value`.
+    fn sign_extend(value: i64, byte_width: usize) -> i64 {
+        let shift = 64 - byte_width as u32 * 8;
+        (value << shift) >> shift
+    }
+    /// Returns the size in bytes of `len` elements of `T`, failing if the size overflows a `usize`.
+    #[inline(always)]
+    fn slice_size<T>(len: usize) -> std::io::Result<usize> {
+        Self::elements_size(std::mem::size_of::<T>(), len)
+    }
+    /// Returns the size in bytes of `len` elements of `size` bytes each, failing if the size overflows
+    /// a `usize`.
+    fn elements_size(size: usize, len: usize) -> std::io::Result<usize> {
+        len.checked_mul(size).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "BufferReader slice size would overflow a usize",
+            )
+        })
+    }
+    /// Checks if there are enough bytes left in the buffer. The error reports the absolute position
+    /// the read was attempted at, and how many bytes it wanted.
+    #[inline(always)]
+    fn check_available(&self, len: usize) -> std::io::Result<()> {
+        self.check_available_for(len, None)
+    }
+    /// Same as `check_available`, but the error also names the type `T` that was being read.
+    #[inline(always)]
+    fn check_available_as<T: ?Sized>(&self, len: usize) -> std::io::Result<()> {
+        self.check_available_for(len, Some(std::any::type_name::<T>()))
+    }
+    /// Checks if there are enough bytes left in the buffer, naming `type_name` in the error if given.
+    fn check_available_for(&self, len: usize, type_name: Option<&str>) -> std::io::Result<()> {
+        if len > self.buffer.len() {
+            let of = type_name.map(|name| format!(" of `{}`", name)).unwrap_or_default();
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                format!(
+                    "BufferReader read{} at offset {:#X} wanted {} bytes, but only {} are available",
+                    of,
+                    self.position(),
+                    len,
+                    self.buffer.len(),
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// A type that can be read from a `BufferReader` field by field, rather than by reinterpreting its
+/// bytes. With the `derive` feature, `#[derive(ReadBe)]` implements this for structs of primitive
+/// integer fields stored in big-endian order.
+pub trait FromBufferReader<'a>: Sized {
+    /// Reads `Self` from the start of the reader's buffer, and advances the reader past it.
+    fn from_buffer_reader(br: &mut BufferReader<'a>) -> std::io::Result<Self>;
+}
+
+/// Returns a reference to the `n` bytes at `offset` in `buf` as a reference to `T`, Where n is the
+/// size of `T`, or `None` if there are not enough bytes in the buffer. This is a one-shot positioned
+/// read that does not need a `BufferReader`, so it can be used in `const` contexts.
+///
+/// Alignment can't be checked in a `const fn`, so `T` must have an alignment of 1, such as a byte
+/// array or a big-endian field type like `U32Be`. Any other `T` fails to compile:
+///
+/// ```compile_fail
+/// let value = buffer_reader::read_t_at::<u64>(&[0; 9], 1);
+/// ```
+pub const fn read_t_at<T: AnyBitPattern>(buf: &[u8], offset: usize) -> Option<&T> {
+    const {
+        assert!(
+            std::mem::align_of::<T>() == 1,
+            "read_t_at can only read types with an alignment of 1"
+        )
+    };
+    let end = match offset.checked_add(std::mem::size_of::<T>()) {
+        Some(end) => end,
+        None => return None,
+    };
+    if end > buf.len() {
+        return None;
+    }
+
+    // SAFETY: We checked that there are at least size of T bytes after offset, T has an alignment of
+    // 1 so any address is aligned for it, and `AnyBitPattern` means any bytes are a valid T.
+    Some(unsafe { &*(buf.as_ptr().add(offset) as *const T) })
+}
+
+#[cfg(feature = "read")]
+use std::io::Read;
 #[cfg(feature = "read")]
 impl Read for BufferReader<'_> {
     /// # Warning - will copy bytes to provided buffer
@@ -234,17 +1999,9 @@ impl Read for BufferReader<'_> {
     /// }
     /// ```
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        match self.check_available(buf.len()) {
-            Ok(_) => {
-                buf.copy_from_slice(self.advance(buf.len()));
-                Ok(buf.len())
-            }
-            Err(_) => {
-                let len = self.len();
-                buf[..len].copy_from_slice(self.advance(len));
-                Ok(len)
-            }
-        }
+        let n = buf.len().min(self.len());
+        buf[..n].copy_from_slice(self.advance(n));
+        Ok(n)
     }
 }
 
@@ -273,6 +2030,98 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "read")]
+    fn read_buf_larger_than_remaining() {
+        let mut br = BufferReader::new(b"Hello");
+
+        let mut buf = [0; 8];
+        assert_eq!(br.read(&mut buf).unwrap(), 5);
+        assert_eq!(&buf, b"Hello\0\0\0");
+        assert_eq!(br.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "read")]
+    fn read_buf_equal_to_remaining() {
+        let mut br = BufferReader::new(b"Hello");
+
+        let mut buf = [0; 5];
+        assert_eq!(br.read(&mut buf).unwrap(), 5);
+        assert_eq!(&buf, b"Hello");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "read")]
+    fn read_buf_smaller_than_remaining() {
+        let mut br = BufferReader::new(b"Hello");
+
+        let mut buf = [0; 3];
+        assert_eq!(br.read(&mut buf).unwrap(), 3);
+        assert_eq!(&buf, b"Hel");
+        assert_eq!(br.peek_remaining(), b"lo");
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn from_mmap() {
+        let hello_world = b"Hello, World!";
+        let mut mmap = memmap2::MmapMut::map_anon(hello_world.len()).unwrap();
+        mmap.copy_from_slice(hello_world);
+        let mmap = mmap.make_read_only().unwrap();
+        let mut br = BufferReader::from_mmap(&mmap);
+
+        let hello = br.read_bytes(5).unwrap();
+        assert_eq!(hello, b"Hello");
+        assert_eq!(br.get_remaining(), b", World!");
+    }
+
+    #[test]
+    fn from_ref_vec() {
+        let hello_world = b"Hello, World!".to_vec();
+        let br = BufferReader::from_ref(&hello_world);
+
+        assert_eq!(br.peek_remaining(), b"Hello, World!");
+    }
+
+    #[test]
+    fn from_ref_string() {
+        let hello_world = String::from("Hello, World!");
+        let br = BufferReader::from_ref(&hello_world);
+
+        assert_eq!(br.peek_remaining(), b"Hello, World!");
+    }
+
+    #[test]
+    fn from_ref_array() {
+        let hello_world = *b"Hello, World!";
+        let br = BufferReader::from_ref(&hello_world);
+
+        assert_eq!(br.peek_remaining(), b"Hello, World!");
+    }
+
+    #[test]
+    fn new_at() {
+        let hello_world = b"Hello, World!";
+        let br = BufferReader::new_at(hello_world, 7).unwrap();
+
+        assert_eq!(br.position(), 7);
+        assert_eq!(br.peek_remaining(), b"World!");
+
+        let end = BufferReader::new_at(hello_world, 13).unwrap();
+        assert!(end.is_empty());
+    }
+
+    #[test]
+    fn new_at_out_of_range() {
+        let hello_world = b"Hello, World!";
+        let err = BufferReader::new_at(hello_world, 14).err().unwrap();
+
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    #[allow(clippy::redundant_slicing)]
     fn read_bytes() {
         let hello_world = b"Hello, World!";
         let mut  br = BufferReader::new(hello_world);
@@ -286,6 +2135,57 @@ mod tests {
         assert_eq!(&world[..], b", World!");
     }
 
+    #[test]
+    fn read_remaining() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        br.read_bytes(7).unwrap();
+
+        assert_eq!(br.read_remaining(), b"World!");
+        assert!(br.is_empty());
+        assert!(br.read_byte().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_bytes_append() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        let mut out = Vec::new();
+
+        br.read_bytes_append(5, &mut out).unwrap();
+        br.read_bytes_append(2, &mut out).unwrap();
+        br.read_bytes_append(6, &mut out).unwrap();
+        assert_eq!(out, hello_world);
+
+        assert!(br.read_bytes_append(1, &mut out).is_err());
+        assert_eq!(out, hello_world);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_bytes_reversed() {
+        let mut br = BufferReader::new(b"olleH!dlroW");
+
+        assert_eq!(br.read_bytes_reversed(5).unwrap(), b"Hello");
+        assert_eq!(br.peek_bytes_reversed(1, 5).unwrap(), b"World");
+        assert_eq!(br.len(), 6);
+        assert!(br.read_bytes_reversed(7).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn concat_remaining() {
+        let mut hello = BufferReader::new(b"Hello, ");
+        let mut world = BufferReader::new(b"World!");
+        hello.read_bytes(5).unwrap();
+        world.read_bytes(5).unwrap();
+
+        assert_eq!(hello.concat_remaining(&world), b", !");
+        assert_eq!(hello.len(), 2);
+        assert_eq!(world.len(), 1);
+    }
+
     #[test]
     fn peek_bytes() {
         let hello_world = b"Hello, World!";
@@ -299,7 +2199,7 @@ mod tests {
 
     /// A test type to make sure read_t and peek_t work.
     #[repr(C, packed(1))]
-    #[derive(Copy, Clone, AnyBitPattern)]
+    #[derive(Copy, Clone, Debug, AnyBitPattern)]
     struct TestT {
         int_one: u32,
         byte: u8,
@@ -319,25 +2219,947 @@ mod tests {
     }
 
     #[test]
-    fn peek_t() {
+    fn read_t_exact() {
         let hello_world = b"Hello, World!";
-        let br = BufferReader::new(hello_world);
-        let test_t = br.peek_t::<TestT>(7).unwrap();
+        let mut br = BufferReader::new(hello_world);
 
-        let int = test_t.int_one;
-        assert_eq!(int, u32::from_le_bytes(*b"Worl"));
-        assert_eq!(test_t.byte, b'd');
+        let test_t = br.read_t_exact::<TestT>(7).unwrap();
+        assert_eq!(test_t.byte, b'o');
+        assert_eq!(br.peek_remaining(), b"World!");
+
+        assert_eq!(br.read_t_exact::<TestT>(4).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.read_t_exact::<TestT>(7).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.len(), 6);
     }
 
     #[test]
-    fn read_byte() {
-        let hello_world = b"Hello, World!";
-        let mut br = BufferReader::new(hello_world);
+    fn read_t_padded() {
+        let mut br = BufferReader::new(b"Hello\0\0\0World\0\x01\0");
+        let test_t = br.read_t_padded::<TestT>(8).unwrap();
+        assert_eq!(test_t.byte, b'o');
+        assert_eq!(br.position(), 8);
+
+        let err = br.read_t_padded::<TestT>(8).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(br.position(), 8);
+        assert_eq!(br.read_t_padded::<TestT>(6).unwrap().byte, b'd');
+    }
+
+    #[test]
+    fn read_t_with_bytes() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        let (test_t, bytes) = br.read_t_with_bytes::<TestT>().unwrap();
+
+        assert_eq!(test_t.byte, b'o');
+        assert_eq!(bytes, b"Hello");
+        assert_eq!(bytes.len(), TEST_T_SIZE);
+        assert_eq!(br.peek_remaining(), b", World!");
+    }
+
+    #[test]
+    fn read_unchecked() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        assert!(br.len() >= TEST_T_SIZE + 8);
+
+        // SAFETY: We checked there are enough bytes for both reads above.
+        let (test_t, world) = unsafe { (br.read_t_unchecked::<TestT>(), br.read_bytes_unchecked(8)) };
+        assert_eq!(test_t.byte, b'o');
+        assert_eq!(world, b", World!");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_t_sized() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+
+        let err = br.read_t_sized::<TestT>(8).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(br.len(), hello_world.len());
+
+        let test_t = br.read_t_sized::<TestT>(TEST_T_SIZE).unwrap();
+        assert_eq!(test_t.byte, b'o');
+    }
+
+    #[repr(u8)]
+    #[derive(Copy, Clone, Debug, PartialEq, bytemuck::CheckedBitPattern)]
+    enum TestFlag {
+        Off = 0,
+        On = 1,
+    }
+
+    #[test]
+    fn read_t_checked_copy() {
+        let mut br = BufferReader::new(&[0x01, 0x00, 0x02]);
+        assert_eq!(br.read_t_checked_copy::<TestFlag>().unwrap(), TestFlag::On);
+        assert_eq!(br.read_t_checked_copy::<TestFlag>().unwrap(), TestFlag::Off);
+
+        let err = br.read_t_checked_copy::<TestFlag>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 1);
+        assert_eq!(br.read_t_checked_copy::<bool>().unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_pair_validated() {
+        // The byte of the `TestT` says how many bytes of the `[u8; 3]` are in use.
+        let records = b"\x01\x00\x00\x00\x02ab\0\x01\x00\x00\x00\x04abc";
+        let mut br = BufferReader::new(records);
+        let fits = |t: &TestT, body: &[u8; 3]| t.byte as usize <= body.len();
+
+        let (test_t, body) = br.read_pair_validated(fits).unwrap();
+        assert_eq!(test_t.byte, 2);
+        assert_eq!(body, b"ab\0");
+
+        let err = br.read_pair_validated(fits).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 8);
+    }
+
+    #[test]
+    fn read_t_validated() {
+        let records = b"\x00\x00\x00\x00a\x01\x00\x00\x00b";
+        let mut br = BufferReader::new(records);
+        let nonzero = |t: &TestT| t.int_one != 0;
+
+        let err = br.read_t_validated(nonzero).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), records.len());
+
+        br.read_bytes(TEST_T_SIZE).unwrap();
+        let test_t = br.read_t_validated(nonzero).unwrap();
+        assert_eq!(test_t.byte, b'b');
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn peek_t() {
+        let hello_world = b"Hello, World!";
+        let br = BufferReader::new(hello_world);
+        let test_t = br.peek_t::<TestT>(7).unwrap();
+
+        let int = test_t.int_one;
+        assert_eq!(int, u32::from_le_bytes(*b"Worl"));
+        assert_eq!(test_t.byte, b'd');
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_vec_t() {
+        let records = b"\x03\x00\x00\x00a\x01\x00\x00\x00b\x02\x00\x00\x00c";
+        let mut br = BufferReader::new(records);
+        let mut test_ts = br.read_vec_t::<TestT>(3).unwrap();
+        test_ts.sort_by_key(|t| t.int_one);
+
+        let bytes: Vec<u8> = test_ts.iter().map(|t| t.byte).collect();
+        assert_eq!(bytes, b"bca");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_vec_t_aligned() {
+        // Put the values one byte past an 8 byte boundary, so they are not aligned for `u64`.
+        let mut words = [0u64; 3];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut words);
+        bytes[1..9].copy_from_slice(&1u64.to_ne_bytes());
+        bytes[9..17].copy_from_slice(&0x0102030405060708u64.to_ne_bytes());
+
+        let mut br = BufferReader::new(&bytes[..17]);
+        br.read_byte().unwrap();
+        let values = br.read_vec_t_aligned::<u64>(2).unwrap();
+        assert_eq!(values, [1, 0x0102030405060708]);
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_slice_t_strided() {
+        let mut br = BufferReader::new(b"Hello\xAA\xAA\xAAWorld\xBB\xBB\xBB!");
+        let records = br.read_slice_t_strided::<TestT>(2, 8).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].byte, b'o');
+        assert_eq!(records[1].byte, b'd');
+        assert_eq!(br.peek_remaining(), b"!");
+
+        assert!(br.read_slice_t_strided::<TestT>(1, 4).is_err());
+        assert!(br.read_slice_t_strided::<TestT>(1, 8).is_err());
+        assert_eq!(br.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_pairs_t() {
+        let pairs = b"\x01\x00\x00\x00\x0A\x00\x02\x00\x00\x00\x14\x00\x03\x00\x00\x00\x1E\x00";
+        let mut br = BufferReader::new(pairs);
+
+        assert_eq!(br.read_pairs_t::<u32, u16>(3).unwrap(), [(1, 10), (2, 20), (3, 30)]);
+        assert!(br.is_empty());
+
+        let mut br = BufferReader::new(pairs);
+        assert!(br.read_pairs_t::<u32, u16>(4).is_err());
+        assert_eq!(br.len(), pairs.len());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_list_of_blobs_u16_le() {
+        let mut br = BufferReader::new(b"\x02\x00\x05\x00Hello\x01\x00!?");
+        let blobs = br.read_list_of_blobs_u16_le().unwrap();
+        assert_eq!(blobs, [&b"Hello"[..], b"!"]);
+        assert_eq!(br.peek_remaining(), b"?");
+
+        let mut br = BufferReader::new(b"\x02\x00\x05\x00Hello\x02\x00!");
+        assert!(br.read_list_of_blobs_u16_le().is_err());
+        assert_eq!(br.position(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_all_tlv_u8u16le() {
+        let mut br = BufferReader::new(b"\x01\x02\x00hi\x02\x00\x00\x03\x03\x00abc");
+        let records = br.read_all_tlv_u8u16le().unwrap();
+        assert_eq!(records, [(1, &b"hi"[..]), (2, b""), (3, b"abc")]);
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_all_tlv_u8u16le_truncated() {
+        let mut br = BufferReader::new(b"\x01\x02\x00hi\x02\x05\x00abc");
+        assert_eq!(br.read_all_tlv_u8u16le().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.position(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_by_offset_table_u32_le() {
+        let data = b"\x0C\x00\x00\x00\x0F\x00\x00\x00\x0F\x00\x00\x00abcdefg";
+        let mut br = BufferReader::new(data);
+        let regions = br.read_by_offset_table_u32_le(3).unwrap();
+        assert_eq!(regions, [&b"abc"[..], b"", b"defg"]);
+        assert_eq!(br.position(), 12);
+
+        let data = b"\x0C\x00\x00\x00\x0B\x00\x00\x00abcdefg";
+        let mut br = BufferReader::new(data);
+        assert!(br.read_by_offset_table_u32_le(2).is_err());
+        assert!(br.read_by_offset_table_u32_le(5).is_err());
+        assert_eq!(br.position(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_optional_fields_t() {
+        let mut br = BufferReader::new(&[0x01, 0x00, 0x03, 0x00, 0xFF]);
+        let fields = br.read_optional_fields_t::<u16>(0b101, 3).unwrap();
+        assert_eq!(fields, [Some(1), None, Some(3)]);
+        assert_eq!(br.len(), 1);
+
+        let mut br = BufferReader::new(&[0x01, 0x00, 0x03]);
+        assert!(br.read_optional_fields_t::<u16>(0b101, 3).is_err());
+        assert_eq!(br.len(), 3);
+        assert!(br.read_optional_fields_t::<u16>(0, 33).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_cobs_frame() {
+        let frames = b"\x03\x11\x22\x02\x33\x00\x01\x00";
+        let mut br = BufferReader::new(frames);
+
+        assert_eq!(br.read_cobs_frame().unwrap(), b"\x11\x22\x00\x33");
+        assert_eq!(br.read_cobs_frame().unwrap(), b"");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_cobs_frame_malformed() {
+        let frame = b"\x05\x11\x00";
+        let mut br = BufferReader::new(frame);
+
+        let err = br.read_cobs_frame().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), frame.len());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_rle_decode() {
+        let encoded = b"\x83\xAA\x02\x01\x02\x82\x00\xFF";
+        let mut br = BufferReader::new(encoded);
+
+        assert_eq!(br.read_rle_decode(7).unwrap(), b"\xAA\xAA\xAA\x01\x02\x00\x00");
+        assert_eq!(br.peek_remaining(), b"\xFF");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_rle_decode_invalid() {
+        let encoded = b"\x83\xAA\x02\x01";
+        let mut br = BufferReader::new(encoded);
+        assert_eq!(br.read_rle_decode(5).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.len(), encoded.len());
+
+        assert_eq!(br.read_rle_decode(2).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), encoded.len());
+
+        let mut br = BufferReader::new(b"\x01a");
+        assert_eq!(br.read_rle_decode(1 << 40).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_hex_bytes() {
+        let mut br = BufferReader::new(b"48656c6C6f!");
+
+        assert_eq!(br.read_hex_bytes(10).unwrap(), b"Hello");
+        assert_eq!(br.peek_remaining(), b"!");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_hex_bytes_invalid() {
+        let mut br = BufferReader::new(b"48656g6c6f");
+
+        assert_eq!(br.read_hex_bytes(10).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.read_hex_bytes(3).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(br.len(), 10);
+    }
+
+    #[test]
+    fn read_fixed_16_16_le() {
+        let mut br = BufferReader::new(b"\x00\x80\x01\x00\x00\x80\xFF\xFF");
+
+        assert_eq!(br.read_fixed_16_16_le().unwrap(), 1.5);
+        assert_eq!(br.read_fixed_16_16_le().unwrap(), -0.5);
+        assert!(br.read_fixed_16_16_le().is_err());
+    }
+
+    #[test]
+    fn read_fixed() {
+        let mut br = BufferReader::new(b"\x40\xF0\x80");
+
+        assert_eq!(br.read_fixed(2, 6).unwrap(), 1.0);
+        assert_eq!(br.read_fixed(8, 8).unwrap(), -127.0625);
+        assert_eq!(br.read_fixed(3, 3).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(br.read_fixed(u32::MAX, 1).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(br.read_fixed(u32::MAX, 9).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(br.len(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "flate")]
+    fn read_deflate() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), Default::default());
+        encoder.write_all(b"Hello, Hello, Hello, World!").unwrap();
+        let mut compressed = encoder.finish().unwrap();
+        let compressed_len = compressed.len();
+        compressed.push(0xFF);
+
+        let mut br = BufferReader::new(&compressed);
+        assert_eq!(br.read_deflate(compressed_len).unwrap(), b"Hello, Hello, Hello, World!");
+        assert_eq!(br.peek_remaining(), b"\xFF");
+    }
+
+    #[test]
+    #[cfg(feature = "flate")]
+    fn read_deflate_invalid() {
+        let mut br = BufferReader::new(b"\xFF\xFF\xFF\xFF");
+
+        assert!(br.read_deflate(4).is_err());
+        assert_eq!(br.len(), 4);
+
+        // An empty final stored block, followed by bytes that aren't part of the stream.
+        let mut br = BufferReader::new(b"\x03\x00garbage");
+        assert_eq!(br.read_deflate(9).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 9);
+    }
+
+    #[test]
+    fn read_signed_le() {
+        let mut br = BufferReader::new(b"\xFE\xFF\xFF\x10\x00\x00\xFF");
+
+        assert_eq!(br.read_signed_le(3).unwrap(), -2);
+        assert_eq!(br.read_signed_le(3).unwrap(), 0x10);
+        assert_eq!(br.read_signed_le(1).unwrap(), -1);
+        assert_eq!(br.read_signed_le(0).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(br.read_signed_le(9).unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn read_signed_be() {
+        let mut br = BufferReader::new(b"\x80\x00\x00\x7F\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF");
+
+        assert_eq!(br.read_signed_be(3).unwrap(), -0x800000);
+        assert_eq!(br.read_signed_be(1).unwrap(), 0x7F);
+        assert_eq!(br.read_signed_be(8).unwrap(), -1);
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_bitset() {
+        let mut br = BufferReader::new(b"\x05\x02\xFF");
+        let bits = br.read_bitset(10).unwrap();
+
+        assert_eq!(
+            bits,
+            [true, false, true, false, false, false, false, false, false, true]
+        );
+        assert_eq!(br.peek_remaining(), b"\xFF");
+    }
+
+    #[test]
+    fn read_uleb128() {
+        let mut br = BufferReader::new(b"\xE5\x8E\x26\x7F");
+
+        assert_eq!(br.read_uleb128().unwrap(), 624485);
+        assert_eq!(br.read_uleb128().unwrap(), 127);
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn peek_uleb128() {
+        let br = BufferReader::new(b"\xE5\x8E\x26\x7F");
+        assert_eq!(br.peek_uleb128().unwrap(), (624485, 3));
+        assert_eq!(br.len(), 4);
+        assert!(BufferReader::new(b"\xE5\x8E").peek_uleb128().is_err());
+    }
+
+    #[test]
+    fn read_uleb128_invalid() {
+        let mut truncated = BufferReader::new(b"\xE5\x8E");
+        assert_eq!(truncated.read_uleb128().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(truncated.len(), 2);
+
+        let mut overflow = BufferReader::new(&[0xFF; 11]);
+        assert_eq!(overflow.read_uleb128().unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(overflow.len(), 11);
+    }
+
+    #[test]
+    fn read_zigzag_i64() {
+        let mut br = BufferReader::new(b"\x00\x01\x02\x03");
+
+        assert_eq!(br.read_zigzag_i64().unwrap(), 0);
+        assert_eq!(br.read_zigzag_i64().unwrap(), -1);
+        assert_eq!(br.read_zigzag_i64().unwrap(), 1);
+        assert_eq!(br.read_zigzag_i64().unwrap(), -2);
+    }
+
+    #[test]
+    fn read_unix_timestamp() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        // 2001-09-09T01:46:40Z, then 1.5 seconds later in milliseconds.
+        let mut br = BufferReader::new(b"\x00\xCA\x9A\x3B\xDC\x15\xA5\xD4\xE8\x00\x00\x00\x00");
+        let secs = br.read_unix_timestamp_secs_le().unwrap();
+        assert_eq!(secs, UNIX_EPOCH + Duration::from_secs(1_000_000_000));
+        let millis = br.read_unix_timestamp_millis_le().unwrap();
+        assert_eq!(millis, secs + Duration::from_millis(1_500));
+        assert!(br.read_unix_timestamp_secs_le().is_err());
+        assert_eq!(br.len(), 1);
+    }
+
+    #[test]
+    fn read_bcd() {
+        let mut br = BufferReader::new(&[0x12, 0x34, 0x09]);
+        assert_eq!(br.read_bcd(2).unwrap(), 1234);
+        assert_eq!(br.read_bcd(1).unwrap(), 9);
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_bcd_invalid() {
+        let mut br = BufferReader::new(&[0x1A, 0x00]);
+        assert_eq!(br.read_bcd(2).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 2);
+
+        let mut br = BufferReader::new(&[0x99; 10]);
+        assert_eq!(br.read_bcd(10).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_tagged_length() {
+        let mut br = BufferReader::new(&[
+            0x25, 0x7B, 0xBD, 0x9D, 0x7F, 0x3E, 0x7D, 0xC2, 0x19, 0x7C, 0x5E, 0xFF, 0x14, 0xE8, 0x8C,
+        ]);
+        assert_eq!(br.read_tagged_length().unwrap(), 37);
+        assert_eq!(br.read_tagged_length().unwrap(), 15293);
+        assert_eq!(br.read_tagged_length().unwrap(), 494_878_333);
+        assert_eq!(br.read_tagged_length().unwrap(), 151_288_809_941_952_652);
+        assert!(br.is_empty());
+
+        let mut br = BufferReader::new(&[0x80, 0x00, 0x00]);
+        assert!(br.read_tagged_length().is_err());
+        assert_eq!(br.len(), 3);
+    }
+
+    #[test]
+    fn read_vlq() {
+        let mut br = BufferReader::new(b"\x7F\x80\x00\x81\x80\x00\xFF\xFF\xFF\x7F");
+
+        assert_eq!(br.read_vlq().unwrap(), 0x7F);
+        assert_eq!(br.read_vlq().unwrap(), 0);
+        assert_eq!(br.read_vlq().unwrap(), 0x4000);
+        assert_eq!(br.read_vlq().unwrap(), 0x0FFF_FFFF);
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_vlq_invalid() {
+        let mut truncated = BufferReader::new(b"\x81\x80");
+        assert_eq!(truncated.read_vlq().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(truncated.len(), 2);
+
+        let mut overflow = BufferReader::new(b"\xFF\xFF\xFF\xFF\xFF\x7F");
+        assert_eq!(overflow.read_vlq().unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(overflow.len(), 6);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_delta_sequence_i64() {
+        // Deltas of 100, -2, 7 and 0, zigzag and LEB128 encoded.
+        let deltas = b"\xC8\x01\x03\x0E\x00";
+        let mut br = BufferReader::new(deltas);
+
+        assert_eq!(br.read_delta_sequence_i64(4, 0).unwrap(), [100, 98, 105, 105]);
+        assert!(br.is_empty());
+
+        let mut br = BufferReader::new(deltas);
+        assert!(br.read_delta_sequence_i64(5, 0).is_err());
+        assert_eq!(br.len(), deltas.len());
+    }
+
+    #[test]
+    fn skip_padding() {
+        let mut br = BufferReader::new(b"\xFF\xFF\xFF\x01");
+
+        br.skip_padding(3, 0xFF).unwrap();
+        assert_eq!(br.read_byte().unwrap(), 0x01);
+    }
+
+    #[test]
+    fn expect_remaining_all() {
+        let mut br = BufferReader::new(b"ab\xFF\xFF\xFF");
+        br.read_bytes(2).unwrap();
+        br.expect_remaining_all(0xFF).unwrap();
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn expect_remaining_all_stray_byte() {
+        let mut br = BufferReader::new(b"\xFF\xFF\x00\xFF");
+        let err = br.expect_remaining_all(0xFF).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains("offset 0x2"));
+        assert_eq!(br.len(), 4);
+    }
+
+    #[test]
+    fn skip_padding_stray_byte() {
+        let mut br = BufferReader::new(b"\x00\x00\x01\x00");
+
+        let err = br.skip_padding(4, 0x00).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "BufferReader expected padding byte 0x00 at offset 0x2, found 0x01"
+        );
+        assert_eq!(br.len(), 4);
+    }
+
+    #[test]
+    fn read_vectored_into() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+
+        let mut hello = [0; 5];
+        let mut world = [0; 10];
+        let read = br
+            .read_vectored_into(&mut [IoSliceMut::new(&mut hello), IoSliceMut::new(&mut world)])
+            .unwrap();
+
+        assert_eq!(read, 13);
+        assert_eq!(&hello, b"Hello");
+        assert_eq!(&world[..8], b", World!");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn expect_t() {
+        let mut br = BufferReader::new(b"\x7FELF\x02");
+
+        let err = br.expect_t(&u32::from_le_bytes(*b"\x7FEXE")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 5);
+
+        br.expect_t(&u32::from_le_bytes(*b"\x7FELF")).unwrap();
+        assert_eq!(br.peek_remaining(), b"\x02");
+    }
+
+    #[test]
+    fn read_t_copy_or_present() {
+        let mut br = BufferReader::new(b"\x01\x00\x00\x00");
+
+        assert_eq!(br.read_t_copy_or::<u32>(7), 1);
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_t_copy_or_absent() {
+        let mut br = BufferReader::new(b"\x01\x00");
+
+        assert_eq!(br.read_t_copy_or::<u32>(7), 7);
+        assert_eq!(br.len(), 2);
+    }
+
+    #[test]
+    fn read_t_at() {
+        const HELLO_WORLD: &[u8] = b"Hello, World!";
+        const WORLD: [u8; 5] = *super::read_t_at::<[u8; 5]>(HELLO_WORLD, 7).unwrap();
+
+        assert_eq!(&WORLD, b"World");
+        assert!(super::read_t_at::<[u8; 4]>(HELLO_WORLD, 10).is_none());
+        assert!(super::read_t_at::<[u8; 4]>(HELLO_WORLD, usize::MAX).is_none());
+
+        // An alignment 1 type can be read at an odd offset.
+        let be = super::read_t_at::<U32Be>(b"\xFF\x12\x34\x56\x78", 1).unwrap();
+        assert_eq!(be.get(), 0x12345678);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_strided_bytes() {
+        // Interleaved 16-bit stereo samples: left, right, left, right.
+        let mut br = BufferReader::new(&[0x01, 0x02, 0xA1, 0xA2, 0x03, 0x04, 0xA3, 0xA4]);
+        assert_eq!(br.read_strided_bytes(2, 4, 2).unwrap(), [0x01, 0x02, 0x03, 0x04]);
+        assert!(br.is_empty());
+
+        let mut br = BufferReader::new(&[0x01, 0x02, 0xA1]);
+        assert!(br.read_strided_bytes(2, 4, 1).is_err());
+        assert!(br.read_strided_bytes(2, 1, 1).is_err());
+        assert_eq!(br.len(), 3);
+    }
+
+    #[test]
+    fn read_line_crlf() {
+        let mut br = BufferReader::new(b"Host: a\r\nAccept: b\nX: c\r\n");
+        assert_eq!(br.read_line_crlf().unwrap(), b"Host: a");
+        assert_eq!(br.read_line_crlf().unwrap(), b"Accept: b\nX: c");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_line_lf_only() {
+        let mut br = BufferReader::new(b"one\ntwo\r\n");
+        assert_eq!(br.read_line_crlf().unwrap(), b"one\ntwo");
+
+        let mut br = BufferReader::new(b"one\ntwo\r\nthree");
+        assert_eq!(br.read_line().unwrap(), b"one");
+        assert_eq!(br.read_line().unwrap(), b"two");
+        assert_eq!(br.read_line().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.read_line_crlf().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.peek_remaining(), b"three");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn read_remaining_lines() {
+        let mut br = BufferReader::new("héllo\r\nwörld\n\nend\n".as_bytes());
+        assert_eq!(br.read_remaining_lines().unwrap(), ["héllo", "wörld", "", "end"]);
+        assert!(br.is_empty());
+        assert!(br.read_remaining_lines().unwrap().is_empty());
+
+        let mut br = BufferReader::new(b"ok\n\xFF\xFE\n");
+        assert_eq!(br.read_remaining_lines().unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 6);
+    }
+
+    #[test]
+    fn read_string() {
+        let mut br = BufferReader::new(b"ab\0\0\x02cd\x03\x00efgh\0abcd");
+        assert_eq!(br.read_string(StringEncoding::FixedNulPadded(4)).unwrap(), b"ab");
+        assert_eq!(br.read_string(StringEncoding::LenPrefixU8).unwrap(), b"cd");
+        assert_eq!(br.read_string(StringEncoding::LenPrefixU16Le).unwrap(), b"efg");
+        assert_eq!(br.read_string(StringEncoding::CStr).unwrap(), b"h");
+        assert_eq!(br.read_string(StringEncoding::FixedNulPadded(4)).unwrap(), b"abcd");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_string_truncated() {
+        let mut br = BufferReader::new(b"\x05\x00abc");
+        assert!(br.read_string(StringEncoding::FixedNulPadded(6)).is_err());
+        assert!(br.read_string(StringEncoding::LenPrefixU8).is_err());
+        assert!(br.read_string(StringEncoding::LenPrefixU16Le).is_err());
+        assert_eq!(br.read_string(StringEncoding::CStr).unwrap(), b"\x05");
+        assert!(br.read_string(StringEncoding::CStr).is_err());
+        assert_eq!(br.len(), 3);
+    }
+
+    #[test]
+    fn read_cstr() {
+        let mut br = BufferReader::new(b"Hello\0World\0!");
+        assert_eq!(br.read_cstr().unwrap(), b"Hello");
+        assert_eq!(br.read_cstr_max(6).unwrap(), b"World");
+        assert_eq!(br.read_cstr().unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 1);
+    }
+
+    #[test]
+    fn read_cstr_max_beyond_bound() {
+        let mut br = BufferReader::new(b"Hello\0");
+        assert_eq!(br.read_cstr_max(5).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 6);
+        assert_eq!(br.read_cstr_max(100).unwrap(), b"Hello");
+    }
+
+    #[test]
+    fn read_pascal_str() {
+        let mut br = BufferReader::new(b"\x05Hello\x06World!\x02");
+
+        assert_eq!(br.read_pascal_str().unwrap(), b"Hello");
+        assert_eq!(br.read_pascal_str_str().unwrap(), "World!");
+        assert_eq!(br.read_pascal_str().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.len(), 1);
+    }
+
+    #[test]
+    fn read_len_prefixed_str() {
+        let mut br = BufferReader::new(b"\x05Hello\x02\x00, \x06\x00\x00\x00World!");
+
+        assert_eq!(br.read_len_prefixed_str_u8().unwrap(), "Hello");
+        assert_eq!(br.read_len_prefixed_str_u16_le().unwrap(), ", ");
+        assert_eq!(br.read_len_prefixed_str_u32_le().unwrap(), "World!");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_len_prefixed_str_invalid() {
+        let mut invalid_utf8 = BufferReader::new(b"\x02\x00\xC3\x28");
+        let err = invalid_utf8.read_len_prefixed_str_u16_le().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(invalid_utf8.len(), 4);
+
+        let mut too_long = BufferReader::new(b"\x06Hello");
+        let err = too_long.read_len_prefixed_str_u8().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(too_long.len(), 6);
+    }
+
+    #[test]
+    fn peek_next_slice_t() {
+        let bytes = b"\x01\x00\x00\x00\x02\x00\x00\x00\x03";
+        let br = BufferReader::new(bytes);
+        let ints = br.peek_next_slice_t::<[u8; 4]>(2).unwrap();
+
+        assert_eq!(u32::from_le_bytes(ints[0]), 1);
+        assert_eq!(u32::from_le_bytes(ints[1]), 2);
+        assert_eq!(br.len(), bytes.len());
+    }
+
+    #[test]
+    fn read_varint_prefixed_slice_t() {
+        // The count is in the second byte of the first word, so the elements stay aligned.
+        let words: [u16; 4] = [u16::from_ne_bytes([0, 3]), 1, 2, 3];
+        let bytes: &[u8] = bytemuck::cast_slice(&words);
+
+        let mut br = BufferReader::new(&bytes[1..]);
+        assert_eq!(br.read_varint_prefixed_slice_t::<u16>().unwrap(), [1, 2, 3]);
+        assert!(br.is_empty());
+
+        let mut br = BufferReader::new(&bytes[1..7]);
+        assert!(br.read_varint_prefixed_slice_t::<u16>().is_err());
+        assert_eq!(br.len(), 6);
+    }
+
+    #[test]
+    fn read_slice_t_until_zero() {
+        let table: [u32; 4] = [0x1000, 0x2000, 0, 0xFFFF];
+        let mut br = BufferReader::new(bytemuck::cast_slice(&table));
+        let offsets = br.read_slice_t_until_zero::<u32>().unwrap();
+
+        assert_eq!(offsets, [0x1000, 0x2000]);
+        assert_eq!(br.len(), 4);
+        assert!(br.read_slice_t_until_zero::<u32>().is_err());
+        assert_eq!(br.len(), 4);
+    }
+
+    #[test]
+    fn read_slice_t_then_rest() {
+        let words: [u32; 3] = [1, 2, u32::from_ne_bytes(*b"rest")];
+        let bytes: &[u8] = bytemuck::cast_slice(&words);
+
+        let mut br = BufferReader::new(bytes);
+        let (ints, rest) = br.read_slice_t_then_rest::<u32>(2).unwrap();
+        assert_eq!(ints, [1, 2]);
+        assert_eq!(rest, b"rest");
+        assert!(br.is_empty());
+
+        let mut br = BufferReader::new(bytes);
+        assert!(br.read_slice_t_then_rest::<u32>(4).is_err());
+        assert_eq!(br.len(), 12);
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone, AnyBitPattern)]
+    struct TestHeader {
+        magic: u32,
+        count: u32,
+    }
+
+    #[test]
+    fn read_header_then_records() {
+        let table: [u32; 6] = [0xCAFE, 3, 10, 20, 30, 0];
+        let bytes: &[u8] = bytemuck::cast_slice(&table);
+        let mut br = BufferReader::new(bytes);
+
+        let count = br.peek_t::<TestHeader>(0).unwrap().count as usize;
+        let (header, records) = br.read_header_then_records::<TestHeader, u32>(count).unwrap();
+        assert_eq!(header.magic, 0xCAFE);
+        assert_eq!(records, [10, 20, 30]);
+        assert_eq!(br.len(), 4);
+
+        let mut br = BufferReader::new(bytes);
+        assert!(br.read_header_then_records::<TestHeader, u32>(5).is_err());
+        assert!(br.read_header_then_records::<TestHeader, u32>(usize::MAX).is_err());
+        assert_eq!(br.position(), 0);
+    }
+
+    #[test]
+    fn peek_remaining_slice_t() {
+        let ints: [u32; 3] = [1, 2, 3];
+        let bytes: &[u8] = bytemuck::cast_slice(&ints);
+
+        let br = BufferReader::new(bytes);
+        assert_eq!(br.peek_remaining_slice_t::<u32>().unwrap(), [1, 2, 3]);
+        assert_eq!(br.len(), 12);
+
+        let br = BufferReader::new(&bytes[..10]);
+        let err = br.peek_remaining_slice_t::<u32>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn cast_remaining() {
+        let ints: [u32; 3] = [1, 2, 3];
+        let bytes: &[u8] = bytemuck::cast_slice(&ints);
+
+        let br = BufferReader::new(bytes);
+        assert_eq!(br.cast_remaining::<u32>().unwrap(), [1, 2, 3]);
+        assert_eq!(br.len(), 12);
+
+        let br = BufferReader::new(&bytes[..10]);
+        assert_eq!(br.cast_remaining::<u32>().unwrap_err().kind(), ErrorKind::InvalidData);
+        let br = BufferReader::new(&bytes[1..9]);
+        assert_eq!(br.cast_remaining::<u32>().unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(Debug, PartialEq, ReadBe)]
+    struct TestBeHeader {
+        magic: u32,
+        version: u16,
+        flags: i8,
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn derive_read_be() {
+        let header = b"\xCA\xFE\xBA\xBE\x00\x02\xFF";
+        let mut br = BufferReader::new(header);
+
+        assert_eq!(
+            TestBeHeader::from_buffer_reader(&mut br).unwrap(),
+            TestBeHeader {
+                magic: 0xCAFEBABE,
+                version: 2,
+                flags: -1,
+            }
+        );
+        assert!(br.is_empty());
+
+        let mut br = BufferReader::new(&header[..6]);
+        assert!(TestBeHeader::from_buffer_reader(&mut br).is_err());
+        assert_eq!(br.len(), 6);
+    }
+
+    #[test]
+    fn peek_t_zst() {
+        let hello_world = b"Hello, World!";
+        let br = BufferReader::new(hello_world);
+
+        assert!(br.peek_t::<()>(13).is_ok());
+        assert!(br.peek_t::<()>(14).is_err());
+        assert!(br.peek_slice_t::<()>(13, 100).is_ok());
+    }
+
+    #[test]
+    fn peek_out_of_bounds() {
+        let hello_world = b"Hello, World!";
+        let br = BufferReader::new(hello_world);
+
+        assert!(br.peek_t::<u8>(usize::MAX).is_err());
+        assert!(br.peek_slice_t::<u8>(usize::MAX, 1).is_err());
+        assert!(br.peek_bytes(usize::MAX, 1).is_err());
+        assert!(br.peek_byte(13).is_err());
+        assert!(br.peek_byte(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn read_byte() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
         let first_byte = br.read_byte().unwrap();
 
         assert_eq!(first_byte, b'H');
     }
 
+    #[derive(Debug, PartialEq)]
+    enum TestEnum {
+        Zero,
+        One,
+        Two,
+    }
+
+    impl TryFrom<u8> for TestEnum {
+        type Error = ();
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(TestEnum::Zero),
+                1 => Ok(TestEnum::One),
+                2 => Ok(TestEnum::Two),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[test]
+    fn read_enum_u8() {
+        let mut br = BufferReader::new(b"\x00\x01\x02\x03");
+
+        assert_eq!(br.read_enum_u8::<TestEnum>().unwrap(), TestEnum::Zero);
+        assert_eq!(br.read_enum_u8::<TestEnum>().unwrap(), TestEnum::One);
+        assert_eq!(br.read_enum_u8::<TestEnum>().unwrap(), TestEnum::Two);
+
+        let err = br.read_enum_u8::<TestEnum>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "BufferReader byte 0x03 is not a valid `buffer_reader::tests::TestEnum`"
+        );
+        assert_eq!(br.len(), 1);
+    }
+
     #[test]
     fn peek_byte() {
         let hello_world = b"Hello, World!";
@@ -347,6 +3169,345 @@ mod tests {
         assert_eq!(seventh_byte, b'W');
     }
 
+    #[test]
+    fn read_t_error_does_not_advance() {
+        let hello_world = b"Hell";
+        let mut br = BufferReader::new(hello_world);
+
+        assert!(br.read_t::<TestT>().is_err());
+        assert_eq!(br.peek_remaining(), b"Hell");
+    }
+
+    #[test]
+    fn read_slice_t_error_does_not_advance() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+
+        assert!(br.read_slice_t::<TestT>(3).is_err());
+        assert!(br.read_slice_t::<TestT>(usize::MAX).is_err());
+        assert_eq!(br.peek_remaining(), b"Hello, World!");
+    }
+
+    #[test]
+    fn read_byte_error_does_not_advance() {
+        let mut br = BufferReader::new(b"");
+
+        assert!(br.read_byte().is_err());
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn read_bytes_error_does_not_advance() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+
+        assert!(br.read_bytes(14).is_err());
+        assert_eq!(br.peek_remaining(), b"Hello, World!");
+    }
+
+    #[test]
+    fn error_position() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        br.read_bytes(7).unwrap();
+        assert_eq!(br.position(), 7);
+
+        let err = br.read_t::<u64>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(
+            err.to_string(),
+            "BufferReader read of `u64` at offset 0x7 wanted 8 bytes, but only 6 are available"
+        );
+
+        let err = br.read_bytes(7).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "BufferReader read at offset 0x7 wanted 7 bytes, but only 6 are available"
+        );
+    }
+
+    #[test]
+    fn error_type_name() {
+        let mut br = BufferReader::new(b"Hi");
+        let err = br.read_t::<TestT>().unwrap_err();
+        assert!(err.to_string().contains("`buffer_reader::tests::TestT`"));
+        let err = br.peek_slice_t::<u16>(0, 2).unwrap_err();
+        assert!(err.to_string().contains("`[u16]`"));
+    }
+
+    #[test]
+    fn require() {
+        let hello_world = b"Hello, World!";
+        let br = BufferReader::new(hello_world);
+
+        assert!(br.require(13).is_ok());
+        assert_eq!(br.require(14).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn bytes_available() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        assert_eq!(br.bytes_available(), 13);
+
+        br.read_bytes(5).unwrap();
+        assert_eq!(br.bytes_available(), 8);
+
+        br.read_byte().unwrap();
+        assert_eq!(br.bytes_available(), 7);
+    }
+
+    #[test]
+    fn is_aligned_for() {
+        let words: [u32; 2] = [1, 2];
+        let mut br = BufferReader::new(bytemuck::cast_slice(&words));
+        assert!(br.is_aligned_for::<u32>());
+        br.read_byte().unwrap();
+        assert!(!br.is_aligned_for::<u32>());
+        assert!(br.is_aligned_for::<u8>());
+        br.read_bytes(3).unwrap();
+        assert!(br.is_aligned_for::<u32>());
+    }
+
+    #[test]
+    fn remaining_count() {
+        let two_and_a_half = [0u8; 10];
+        let br = BufferReader::new(&two_and_a_half);
+
+        assert_eq!(br.remaining_count::<u32>(), 2);
+        assert_eq!(br.remaining_count::<()>(), usize::MAX);
+    }
+
+    #[test]
+    fn peek_byte_opt() {
+        let hello_world = b"Hello, World!";
+        let br = BufferReader::new(hello_world);
+
+        assert_eq!(br.peek_byte_opt(7), Some(b'W'));
+        assert_eq!(br.peek_byte_opt(13), None);
+        assert_eq!(br.peek_byte_opt(usize::MAX), None);
+    }
+
+    #[test]
+    fn transaction() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+
+        let err = br
+            .transaction(|br| {
+                br.read_t::<TestT>()?;
+                br.read_bytes(2)?;
+                br.read_bytes(7)
+            })
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.position(), 0);
+
+        let world = br
+            .transaction(|br| {
+                br.read_t::<TestT>()?;
+                br.read_bytes(2)?;
+                br.read_bytes(6)
+            })
+            .unwrap();
+        assert_eq!(world, b"World!");
+        assert!(br.is_empty());
+    }
+
+    #[test]
+    fn abs_rel_offset() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        br.read_t::<TestT>().unwrap();
+        br.read_bytes(2).unwrap();
+
+        assert_eq!(br.abs_offset(0), Some(7));
+        assert_eq!(br.abs_offset(3), Some(10));
+        assert_eq!(br.abs_offset(usize::MAX), None);
+        assert_eq!(br.rel_offset(10).unwrap(), 3);
+        assert_eq!(br.rel_offset(13).unwrap(), 6);
+        assert_eq!(br.rel_offset(6).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(br.rel_offset(14).unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn enumerate_bytes() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        br.read_bytes(7).unwrap();
+        let mut bytes = br.enumerate_bytes();
+
+        assert_eq!(bytes.next(), Some((br.position(), b'W')));
+        assert_eq!(bytes.last(), Some((12, b'!')));
+        assert_eq!(br.len(), 6);
+    }
+
+    #[test]
+    fn matches_at() {
+        let hello_world = b"Hello, World!";
+        let br = BufferReader::new(hello_world);
+
+        assert!(br.matches_at(7, b"World"));
+        assert!(!br.matches_at(7, b"world"));
+        assert!(!br.matches_at(10, b"ld!!"));
+        assert!(!br.matches_at(usize::MAX, b"!"));
+    }
+
+    #[test]
+    fn split_remaining() {
+        let br = BufferReader::new(b"id,name,,value");
+        let fields: Vec<&[u8]> = br.split_remaining(b',').collect();
+
+        assert_eq!(fields, [&b"id"[..], b"name", b"", b"value"]);
+        assert_eq!(br.len(), 14);
+    }
+
+    #[test]
+    fn find_t() {
+        let records = b"\x01\x00\x00\x00a\x02\x00\x00\x00b\x03\x00\x00\x00c";
+        let br = BufferReader::new(records);
+
+        assert_eq!(br.find_t(|t: &TestT| t.byte == b'b'), Some(TEST_T_SIZE));
+        assert_eq!(br.find_t(|t: &TestT| { t.int_one } == 4), None);
+        assert_eq!(br.len(), records.len());
+    }
+
+    #[test]
+    fn read_versioned() {
+        fn parse(br: &mut BufferReader) -> std::io::Result<(u8, Option<u8>)> {
+            br.read_versioned(|version, br| match version {
+                1 => Ok((br.read_byte()?, None)),
+                2 => Ok((br.read_byte()?, Some(br.read_byte()?))),
+                _ => Err(Error::new(ErrorKind::InvalidData, "unknown version")),
+            })
+        }
+
+        let mut br = BufferReader::new(b"\x01\x00\x00\x00\x0A\x02\x00\x00\x00\x0B\x0C\x03\x00\x00\x00");
+        assert_eq!(parse(&mut br).unwrap(), (0x0A, None));
+        assert_eq!(parse(&mut br).unwrap(), (0x0B, Some(0x0C)));
+        assert_eq!(parse(&mut br).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(br.len(), 4);
+    }
+
+    #[test]
+    fn checkpoint_restore() {
+        let mut br = BufferReader::new(b"Hello, World!");
+        br.read_bytes(7).unwrap();
+        let cp = br.checkpoint();
+        assert_eq!(cp.position(), 7);
+        assert_eq!(br.read_bytes(5).unwrap(), b"World");
+        br.restore(cp);
+        assert_eq!(br.position(), 7);
+        assert_eq!(br.peek_remaining(), b"World!");
+    }
+
+    #[test]
+    fn footer_reserve() {
+        let mut br = BufferReader::new(b"HDRbodyFOOT");
+        assert_eq!(br.read_bytes(3).unwrap(), b"HDR");
+        br.set_footer_reserve(4).unwrap();
+        assert_eq!(br.len(), 4);
+        assert_eq!(br.position(), 3);
+        assert!(br.read_bytes(5).is_err());
+        assert_eq!(br.read_remaining(), b"body");
+        assert!(br.is_empty());
+        assert!(br.read_byte().is_err());
+        assert_eq!(br.read_footer(), b"FOOT");
+        assert_eq!(br.position(), 7);
+
+        assert!(br.set_footer_reserve(5).is_err());
+        br.set_footer_reserve(0).unwrap();
+        assert_eq!(br.read_remaining(), b"FOOT");
+        assert_eq!(br.read_footer(), b"");
+    }
+
+    #[test]
+    fn restore_state() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        br.read_bytes(7).unwrap();
+        let state = br.state();
+        assert_eq!(state, ReaderState { position: 7 });
+
+        let mut resumed = BufferReader::restore_state(hello_world, state).unwrap();
+        assert_eq!(resumed.position(), 7);
+        assert_eq!(resumed.read_bytes(5).unwrap(), b"World");
+        assert!(BufferReader::restore_state(b"Hello", state).is_err());
+    }
+
+    #[test]
+    fn fork() {
+        let mut br = BufferReader::new(b"Hello, World!");
+        br.read_bytes(2).unwrap();
+
+        let mut fork = br.fork();
+        assert_eq!(fork.read_bytes(5).unwrap(), b"llo, ");
+        assert_eq!(br.position(), 2);
+
+        br.set_position(fork.position()).unwrap();
+        assert_eq!(br.peek_remaining(), b"World!");
+        assert!(br.set_position(14).is_err());
+        br.set_position(0).unwrap();
+        assert_eq!(br.len(), 13);
+    }
+
+    #[test]
+    fn rewind() {
+        let mut br = BufferReader::new(b"Hello, World!");
+        assert_eq!(br.read_bytes(8).unwrap(), b"Hello, W");
+        br.rewind(4).unwrap();
+        assert_eq!(br.position(), 4);
+        assert_eq!(br.read_bytes(4).unwrap(), b"o, W");
+        assert_eq!(br.rewind(9).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(br.position(), 8);
+    }
+
+    #[test]
+    fn footer_reserve_transaction() {
+        let mut br = BufferReader::new(b"0123456789");
+        let result = br.transaction(|br| {
+            br.set_footer_reserve(4)?;
+            br.read_bytes(7).map(|_| ())
+        });
+        assert!(result.is_err());
+        assert_eq!(br.position(), 0);
+        assert_eq!(br.len(), 10);
+        assert_eq!(br.read_footer(), b"");
+    }
+
+    #[test]
+    fn fnv1a_since() {
+        let mut br = BufferReader::new(b"xxfoobar");
+        br.read_bytes(2).unwrap();
+        let cp = br.checkpoint();
+        assert_eq!(br.fnv1a_since(cp), 0xCBF2_9CE4_8422_2325);
+        br.read_bytes(6).unwrap();
+        assert_eq!(br.fnv1a_since(cp), 0x8594_4171_F739_67E8);
+    }
+
+    #[test]
+    fn read_union() {
+        #[derive(Debug, PartialEq)]
+        enum Value<'a> {
+            Int(u32),
+            Str(&'a str),
+        }
+
+        fn parse<'a>(br: &mut BufferReader<'a>) -> std::io::Result<Value<'a>> {
+            br.read_union(|tag, br| match tag {
+                0 => Ok(Value::Int(br.read_int_le()?)),
+                1 => Ok(Value::Str(br.read_len_prefixed_str_u8()?)),
+                _ => Err(Error::new(ErrorKind::InvalidData, "unknown tag")),
+            })
+        }
+
+        let mut br = BufferReader::new(b"\x00\x2A\x00\x00\x00\x01\x02hi\x01\x05hi");
+        assert_eq!(parse(&mut br).unwrap(), Value::Int(42));
+        assert_eq!(parse(&mut br).unwrap(), Value::Str("hi"));
+        assert_eq!(parse(&mut br).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.len(), 4);
+    }
+
     #[test]
     fn find() {
         let hello_world = b"Hello, World!";
@@ -356,6 +3517,46 @@ mod tests {
         assert_eq!(hello, 4);
     }
 
+    #[test]
+    fn peek_checksum_u8() {
+        let br = BufferReader::new(&[0x01, 0x02, 0x04, 0xFF, 0x10]);
+        assert_eq!(br.peek_checksum_u8(3).unwrap(), 0x07);
+        assert_eq!(br.peek_checksum_u8(4).unwrap(), 0xF8);
+        assert_eq!(br.peek_checksum_sum_u8(4).unwrap(), 0x06);
+        assert_eq!(br.peek_checksum_u8(0).unwrap(), 0);
+        assert!(br.peek_checksum_u8(6).is_err());
+        assert_eq!(br.len(), 5);
+    }
+
+    #[test]
+    fn count_byte() {
+        let mut br = BufferReader::new(b"one\ntwo\nthree\n");
+        assert_eq!(br.count_byte(b'\n'), 3);
+        br.read_bytes(4).unwrap();
+        assert_eq!(br.count_byte(b'\n'), 2);
+        assert_eq!(br.count_byte(b'x'), 0);
+    }
+
+    #[test]
+    fn distance_to_bytes() {
+        let mut br = BufferReader::new(b"\xAArecord one\xAArecord two");
+        br.read_byte().unwrap();
+        assert_eq!(br.distance_to_bytes(b"\xAA"), Some(10));
+        assert_eq!(br.distance_to_bytes(b"three"), None);
+        assert_eq!(br.position(), 1);
+    }
+
+    #[test]
+    fn find_empty() {
+        let br = BufferReader::new(b"");
+        assert_eq!(br.find_bytes(b"a"), None);
+        assert_eq!(br.find_bytes(b""), Some(0));
+
+        let br = BufferReader::new(b"Hi");
+        assert_eq!(br.find_bytes(b""), Some(0));
+        assert_eq!(br.find_bytes(b"Hi!"), None);
+    }
+
     #[test]
     fn find_end() {
         let hello_world = b"Hello, World!";
@@ -365,6 +3566,47 @@ mod tests {
         assert_eq!(hello, 11);
     }
 
+    #[test]
+    fn after_bytes() {
+        let hello_world = b"Hello, World!";
+        let br = BufferReader::new(hello_world);
+
+        assert_eq!(br.after_bytes(b", ").unwrap(), b"World!");
+        assert_eq!(br.after_bytes(b"!!"), None);
+        assert_eq!(br.len(), hello_world.len());
+    }
+
+    #[test]
+    fn seek_after_bytes() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+
+        assert_eq!(br.seek_after_bytes(b"!!"), None);
+        assert_eq!(br.len(), hello_world.len());
+
+        assert_eq!(br.seek_after_bytes(b", "), Some(7));
+        assert_eq!(br.peek_remaining(), b"World!");
+    }
+
+    #[test]
+    fn discard_until_bytes() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+
+        assert_eq!(br.discard_until_bytes(b"Wo").unwrap(), 7);
+        assert_eq!(br.peek_remaining(), b"World!");
+    }
+
+    #[test]
+    fn discard_until_bytes_not_found() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+
+        let err = br.discard_until_bytes(b"!!").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(br.len(), hello_world.len());
+    }
+
     #[test]
     #[should_panic]
     fn find_end_panic() {
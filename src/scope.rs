@@ -0,0 +1,64 @@
+use crate::BufferReader;
+use std::ops::{Deref, DerefMut};
+
+/// A guard returned by `BufferReader::scoped` holding a child reader over a single record. When the
+/// guard is dropped, the parent reader is advanced to the end of the record, no matter how much of
+/// the record the child read. The child's positions are relative to the start of the record.
+pub struct RecordScope<'p, 'a> {
+    parent: &'p mut BufferReader<'a>,
+    child: BufferReader<'a>,
+    record_len: usize,
+}
+
+impl<'p, 'a> RecordScope<'p, 'a> {
+    pub(crate) fn new(parent: &'p mut BufferReader<'a>, record_len: usize) -> std::io::Result<Self> {
+        let child = BufferReader::new(parent.peek_bytes(0, record_len)?);
+        Ok(RecordScope {
+            parent,
+            child,
+            record_len,
+        })
+    }
+}
+
+impl<'a> Deref for RecordScope<'_, 'a> {
+    type Target = BufferReader<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.child
+    }
+}
+
+impl DerefMut for RecordScope<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.child
+    }
+}
+
+impl Drop for RecordScope<'_, '_> {
+    fn drop(&mut self) {
+        // The record was bounds checked when the scope was created.
+        self.parent.advance(self.record_len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufferReader;
+
+    #[test]
+    fn scoped() {
+        let records = b"\x01\x02\x03\x04\x05\x06\x07\x08";
+        let mut br = BufferReader::new(records);
+        {
+            let mut record = br.scoped(4).unwrap();
+            assert_eq!(record.read_byte().unwrap(), 0x01);
+            assert_eq!(record.len(), 3);
+        }
+
+        assert_eq!(br.position(), 4);
+        assert_eq!(br.peek_remaining(), b"\x05\x06\x07\x08");
+        assert!(br.scoped(5).is_err());
+        assert_eq!(br.position(), 4);
+    }
+}
@@ -0,0 +1,12 @@
+/// How a string is stored in the buffer, for use with `BufferReader::read_string`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StringEncoding {
+    /// A field of exactly this many bytes, where the string ends at the first NUL, if there is one.
+    FixedNulPadded(usize),
+    /// A `u8` length followed by that many bytes, like `BufferReader::read_pascal_str`.
+    LenPrefixU8,
+    /// A little-endian `u16` length followed by that many bytes.
+    LenPrefixU16Le,
+    /// A NUL terminated string, like `BufferReader::read_cstr`.
+    CStr,
+}
@@ -0,0 +1,97 @@
+use crate::BufferReader;
+
+/// Splits the buffer of a `BufferReader` into delimited records and fields, such as the lines and
+/// columns of a CSV style log. Each call returns a reference into the original buffer and advances
+/// the reader past the returned token and its separator.
+pub struct RecordTokenizer<'a> {
+    reader: BufferReader<'a>,
+    trailing_field: bool,
+}
+
+impl<'a> RecordTokenizer<'a> {
+    /// Returns a new `RecordTokenizer<'a>` over the remaining bytes of `reader`.
+    #[inline(always)]
+    pub fn new(reader: BufferReader<'a>) -> Self {
+        RecordTokenizer {
+            reader,
+            trailing_field: false,
+        }
+    }
+    /// Returns the bytes up to the next `sep`, or to the end of the buffer if there isn't one. A
+    /// trailing `sep` is followed by one last empty field, so `a,b,` has three fields. Returns `None`
+    /// once every field has been returned.
+    pub fn next_field(&mut self, sep: u8) -> Option<&'a [u8]> {
+        if self.reader.is_empty() {
+            return std::mem::take(&mut self.trailing_field).then_some(&[]);
+        }
+
+        Some(self.next_token(sep))
+    }
+    /// Returns the bytes up to the next `rec_sep`, or to the end of the buffer if there isn't one.
+    /// Unlike `next_field`, a trailing `rec_sep` just ends the last record, so `a\nb\n` has two
+    /// records. Returns `None` once the buffer is empty.
+    pub fn next_record(&mut self, rec_sep: u8) -> Option<&'a [u8]> {
+        if self.reader.is_empty() {
+            return None;
+        }
+
+        let record = self.next_token(rec_sep);
+        self.trailing_field = false;
+        Some(record)
+    }
+    /// Returns the reader, positioned after the last token returned.
+    #[inline(always)]
+    pub fn into_inner(self) -> BufferReader<'a> {
+        self.reader
+    }
+    /// Returns the bytes up to the next `sep`, and advances the reader past them and the `sep`. If the
+    /// `sep` was the last byte of the buffer, an empty field is still to come.
+    fn next_token(&mut self, sep: u8) -> &'a [u8] {
+        let remaining = self.reader.peek_remaining();
+        match remaining.iter().position(|&b| b == sep) {
+            Some(len) => {
+                let token = self.reader.advance(len);
+                self.reader.advance(1);
+                self.trailing_field = self.reader.is_empty();
+                token
+            }
+            None => {
+                self.trailing_field = false;
+                self.reader.advance(remaining.len())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize() {
+        let mut records = RecordTokenizer::new(BufferReader::new(b"a,b,c\n1,2,3\n"));
+        let mut rows = vec![];
+        while let Some(record) = records.next_record(b'\n') {
+            let mut fields = RecordTokenizer::new(BufferReader::new(record));
+            let mut row = vec![];
+            while let Some(field) = fields.next_field(b',') {
+                row.push(field);
+            }
+
+            rows.push(row);
+        }
+
+        assert_eq!(rows, [[b"a", b"b", b"c"], [b"1", b"2", b"3"]]);
+        assert!(records.into_inner().is_empty());
+    }
+
+    #[test]
+    fn trailing_field() {
+        let mut fields = RecordTokenizer::new(BufferReader::new(b"a,,b,"));
+        assert_eq!(fields.next_field(b','), Some(&b"a"[..]));
+        assert_eq!(fields.next_field(b','), Some(&b""[..]));
+        assert_eq!(fields.next_field(b','), Some(&b"b"[..]));
+        assert_eq!(fields.next_field(b','), Some(&b""[..]));
+        assert_eq!(fields.next_field(b','), None);
+    }
+}
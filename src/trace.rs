@@ -0,0 +1,65 @@
+/// Whether a traced operation advanced the reader or only looked at the buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReadKind {
+    Read,
+    Peek,
+}
+
+/// A single read or peek recorded by a `BufferReader` when the `trace` feature is enabled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ReadEvent {
+    /// The absolute offset into the original slice that the operation started at.
+    pub offset: usize,
+    /// The number of bytes the operation touched.
+    pub len: usize,
+    /// Whether the operation advanced the reader.
+    pub kind: ReadKind,
+    /// The name of the type that was read, as returned by `std::any::type_name`.
+    pub type_name: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BufferReader;
+
+    #[test]
+    fn trace() {
+        let hello_world = b"Hello, World!";
+        let mut br = BufferReader::new(hello_world);
+        br.read_bytes(5).unwrap();
+        br.peek_t::<[u8; 2]>(0).unwrap();
+        br.read_byte().unwrap();
+        br.peek_byte(1).unwrap();
+        br.read_t::<[u8; 4]>().unwrap();
+
+        let event = |offset, len, kind, type_name| ReadEvent { offset, len, kind, type_name };
+        assert_eq!(
+            br.trace(),
+            [
+                event(0, 5, ReadKind::Read, "[u8]"),
+                event(5, 2, ReadKind::Peek, "[u8; 2]"),
+                event(5, 1, ReadKind::Read, "u8"),
+                event(7, 1, ReadKind::Peek, "u8"),
+                event(6, 4, ReadKind::Read, "[u8; 4]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_while_peeking() {
+        let br = BufferReader::new(b"Hello");
+        let trace = br.trace();
+        br.peek_byte(0).unwrap();
+        assert!(trace.is_empty());
+
+        assert_eq!(br.take_trace().len(), 1);
+        assert!(br.trace().is_empty());
+    }
+
+    #[test]
+    fn reader_is_sync() {
+        fn assert_sync<T: Sync + Send>() {}
+        assert_sync::<BufferReader>();
+    }
+}
@@ -0,0 +1,137 @@
+use bytemuck::NoUninit;
+use std::io::{Error, ErrorKind};
+
+/// The writing counterpart of `BufferReader`, used for serializing C structures into a contiguous
+/// buffer of memory. Each `write_*` method copies its value at the cursor and advances the cursor
+/// past it.
+///
+/// # Errors
+///
+/// Every `write_*` method checks that there is enough room left in the buffer before it writes. If a
+/// write fails, nothing is written and the writer is left exactly where it was before the call.
+pub struct BufferWriter<'a> {
+    buffer: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> BufferWriter<'a> {
+    /// Returns a new `BufferWriter<'a>` that writes to the start of the provided slice.
+    #[inline(always)]
+    pub fn new(slice: &'a mut [u8]) -> Self {
+        BufferWriter {
+            buffer: slice,
+            position: 0,
+        }
+    }
+    /// Copies the bytes of `t` to the buffer, and then advances the cursor by the size of `T` in
+    /// bytes. Function will fail if there is not enough room left in the buffer.
+    #[inline(always)]
+    pub fn write_t<T: NoUninit>(&mut self, t: &T) -> std::io::Result<()> {
+        self.write_bytes(bytemuck::bytes_of(t))
+    }
+    /// Copies the bytes of every element of `slice` to the buffer, and then advances the cursor by the
+    /// size of `T` * `slice.len()` in bytes. Function will fail if there is not enough room left in
+    /// the buffer.
+    #[inline(always)]
+    pub fn write_slice_t<T: NoUninit>(&mut self, slice: &[T]) -> std::io::Result<()> {
+        self.write_bytes(bytemuck::cast_slice(slice))
+    }
+    /// Copies `bytes` to the buffer, and then advances the cursor by `bytes.len()`. Function will fail
+    /// if there is not enough room left in the buffer.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        let end = self.position + bytes.len();
+        if end > self.buffer.len() {
+            return Err(Error::new(
+                ErrorKind::WriteZero,
+                format!(
+                    "BufferWriter write at offset {:#X} wanted {} bytes, but only {} are available",
+                    self.position,
+                    bytes.len(),
+                    self.len(),
+                ),
+            ));
+        }
+
+        self.buffer[self.position..end].copy_from_slice(bytes);
+        self.position = end;
+        Ok(())
+    }
+    /// Writes `byte` to the buffer, and then advances the cursor by one. Function will fail if the
+    /// buffer is full.
+    #[inline(always)]
+    pub fn write_byte(&mut self, byte: u8) -> std::io::Result<()> {
+        self.write_bytes(&[byte])
+    }
+    /// Returns the number of bytes of room left in the buffer.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.buffer.len() - self.position
+    }
+    /// Returns true if the buffer is full.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the number of bytes that have been written since the writer was created.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+    /// Returns a reference to the bytes that have been written so far.
+    #[inline(always)]
+    pub fn written(&self) -> &[u8] {
+        &self.buffer[..self.position]
+    }
+    /// Consumes the writer, and returns a reference to the bytes that were written.
+    #[inline(always)]
+    pub fn into_written(self) -> &'a [u8] {
+        &self.buffer[..self.position]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BufferReader;
+    use bytemuck::{AnyBitPattern, NoUninit};
+
+    #[repr(C, packed(1))]
+    #[derive(Copy, Clone, Debug, PartialEq, AnyBitPattern, NoUninit)]
+    struct Record {
+        id: u32,
+        flags: u8,
+    }
+
+    #[test]
+    fn round_trip() {
+        let record = Record { id: 7, flags: 0x80 };
+        let mut buf = [0; 12];
+        let mut bw = BufferWriter::new(&mut buf);
+        bw.write_byte(0xAA).unwrap();
+        bw.write_t(&record).unwrap();
+        bw.write_slice_t(&[1u16, 2, 3]).unwrap();
+        assert_eq!(bw.position(), 12);
+        assert!(bw.is_empty());
+
+        let mut br = BufferReader::new(bw.into_written());
+        assert_eq!(br.read_byte().unwrap(), 0xAA);
+        assert_eq!(br.read_t_copy_or(Record { id: 0, flags: 0 }), record);
+        for i in 1..=3u16 {
+            assert_eq!(br.read_t_copy_or(0u16), i);
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let mut buf = [0; 4];
+        let mut bw = BufferWriter::new(&mut buf);
+        bw.write_bytes(b"abc").unwrap();
+
+        let err = bw.write_t(&0u16).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        assert_eq!(bw.written(), b"abc");
+        bw.write_byte(b'd').unwrap();
+        assert!(bw.write_byte(b'e').is_err());
+        assert_eq!(buf, *b"abcd");
+    }
+}